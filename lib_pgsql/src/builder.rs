@@ -0,0 +1,1011 @@
+use crate::common::{
+    QueryType, SQLAggregate, SQLCondition, SQLConflict, SQLError, SQLIdentifier, SQLJoin, SQLLimit,
+    SQLOrder, SQLSort,
+};
+use crate::dpqueryable::DPQueryable;
+use crate::executor::PgExecutor;
+use futures_util::stream::BoxStream;
+use postgres_from_row::FromRow;
+use std::marker::PhantomData;
+use tokio_postgres::{
+    types::{FromSql, ToSql},
+    Row,
+};
+
+/// Values of `$n` params of a query
+type Params<'a> = Vec<&'a (dyn ToSql + Sync)>;
+
+/// This `struct` keeps a list of conditions together with their values, so the number of `$n` params always
+/// matches the values
+#[derive(Default)]
+struct Filter<'a> {
+    conditions: Vec<SQLCondition<'a>>,
+    values: Params<'a>,
+    error: Option<String>,
+}
+
+impl<'a> Filter<'a> {
+    /// Adds `condition` with `AND` (or `OR`); previous conditions are grouped first when needed,
+    /// so conditions are always combined from left to right
+    fn push(
+        &mut self,
+        separator: SQLCondition<'a>,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) {
+        if condition.param_count() != values.len() {
+            self.error.get_or_insert(format!(
+                "Condition {:?} needs {} values, but {} values are provided!",
+                condition,
+                condition.param_count(),
+                values.len()
+            ));
+        }
+        if !self.conditions.is_empty() {
+            if matches!(separator, SQLCondition::AND)
+                && self
+                    .conditions
+                    .iter()
+                    .any(|item| matches!(item, SQLCondition::OR))
+            {
+                self.conditions = vec![SQLCondition::GROUP(std::mem::take(&mut self.conditions))];
+            }
+            self.conditions.push(separator);
+        }
+        self.conditions.push(condition);
+        self.values.extend_from_slice(values);
+    }
+
+    /// Returns conditions (`None` if empty) and their values, or the first error of added conditions
+    fn build(self) -> Result<(Option<Vec<SQLCondition<'a>>>, Params<'a>), SQLError> {
+        if let Some(error) = self.error {
+            return Err(error.into());
+        }
+        match self.conditions.is_empty() {
+            true => Ok((None, self.values)),
+            false => Ok((Some(self.conditions), self.values)),
+        }
+    }
+}
+
+/// This `struct` provides a fluent `SELECT` query builder; it is created by `DPQueryable::select_builder()`
+///
+/// Each filter takes its own values, so they can not get out of sync with the `$n` params
+///
+/// ```no_run
+/// let rows = ExampleTable::select_builder()
+///     .filter(SQLCondition::GE("id"), &[&100i64])
+///     .or_filter(ExampleTable::NAME.like(), &[&"a%"])
+///     .order_by(ExampleTable::ID.desc())
+///     .limit(10)
+///     .fetch_all(&pool)
+///     .await?;
+/// ```
+///
+/// Joined tables are added by [`join`]; fields of the filters and sorts can be qualified with aliases
+///
+/// ```no_run
+/// let rows: Vec<(Order, Option<Customer>)> = Order::select_builder()
+///     .alias("o")
+///     .join(SQLJoin::left("public.customers", "c").on("c.id", "o.customer_id"))
+///     .filter(SQLCondition::GE("o.total"), &[&100f64])
+///     .fetch_pairs::<Customer, _>(&pool, "c")
+///     .await?;
+/// ```
+///
+/// [`join`]: #method.join
+pub struct Select<'a, T> {
+    table_name: Option<&'a str>,
+    alias: Option<&'a str>,
+    join_list: Vec<SQLJoin<'a>>,
+    field_list: Option<Vec<&'a str>>,
+    filter: Filter<'a>,
+    sort_list: Vec<SQLOrder<'a>>,
+    sort_type: Option<SQLSort>,
+    limit: Option<SQLLimit>,
+    table: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Select<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    /// Creates a `SELECT` query of all rows of `T::table_name()`
+    pub fn new() -> Self {
+        Self {
+            table_name: None,
+            alias: None,
+            join_list: Vec::new(),
+            field_list: None,
+            filter: Filter::default(),
+            sort_list: Vec::new(),
+            sort_type: None,
+            limit: None,
+            table: PhantomData,
+        }
+    }
+
+    /// Selects from `table_name` instead of `T::table_name()`
+    pub fn table(mut self, table_name: &'a str) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// Sets an alias for the table (`FROM table_name AS alias`); all fields of `T` are qualified with it
+    pub fn alias(mut self, alias: &'a str) -> Self {
+        self.alias = Some(alias);
+        self
+    }
+
+    /// Adds an `INNER JOIN` or `LEFT JOIN` (e.g. `SQLJoin::inner("public.customers", "c").on("c.id", "o.customer_id")`)
+    pub fn join(mut self, join: SQLJoin<'a>) -> Self {
+        self.join_list.push(join);
+        self
+    }
+
+    /// Selects `field_list` instead of all fields (only useful with [`fetch_rows`] and [`fetch_as`]), e.g.
+    /// `vec!["id", "name"]` or `vec![ExampleTable::ID, ExampleTable::NAME]`
+    ///
+    /// [`fetch_rows`]: #method.fetch_rows
+    /// [`fetch_as`]: #method.fetch_as
+    pub fn fields<F>(mut self, field_list: Vec<F>) -> Self
+    where
+        F: Into<&'a str>,
+    {
+        self.field_list = Some(field_list.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Adds `condition` with `AND`; `values` are the values of its `$n` params
+    pub fn filter(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.filter.push(SQLCondition::AND, condition, values);
+        self
+    }
+
+    /// Adds `condition` with `OR`; `values` are the values of its `$n` params
+    pub fn or_filter(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.filter.push(SQLCondition::OR, condition, values);
+        self
+    }
+
+    /// Adds a sort field (e.g. `"id".into()`, `SQLOrder::desc("id")` or `ExampleTable::ID.desc()`)
+    pub fn order_by<O>(mut self, order: O) -> Self
+    where
+        O: Into<SQLOrder<'a>>,
+    {
+        self.sort_list.push(order.into());
+        self
+    }
+
+    /// Sets the direction of sort fields without their own direction (`ASC` by default)
+    pub fn sort(mut self, sort_type: SQLSort) -> Self {
+        self.sort_type = Some(sort_type);
+        self
+    }
+
+    /// Returns maximum `limit` rows
+    pub fn limit(mut self, limit: i64) -> Self {
+        let offset = self.limit.map_or(0, |item| item.offset);
+        self.limit = Some(SQLLimit::new(limit, offset));
+        self
+    }
+
+    /// Skips the first `offset` rows
+    pub fn offset(mut self, offset: i64) -> Self {
+        let limit = self.limit.map_or(i64::MAX, |item| item.limit);
+        self.limit = Some(SQLLimit::new(limit, offset));
+        self
+    }
+
+    /// Sets both `LIMIT` and `OFFSET` (e.g. `SQLLimit::page(2, 50)`)
+    pub fn page(mut self, limit: SQLLimit) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns the query and its values
+    pub fn build(self) -> Result<(String, Params<'a>), SQLError> {
+        let fields = match (&self.field_list, self.alias, self.join_list.is_empty()) {
+            (None, None, false) => {
+                T::alias_field_query_builder(self.table_name.unwrap_or(T::table_name()))?
+            }
+            (None, Some(alias), _) => T::alias_field_query_builder(alias)?,
+            _ => T::field_query_builder(self.field_list.clone())?,
+        };
+        self.build_fields(fields)
+    }
+
+    /// Returns the query with already quoted `fields` and its values
+    fn build_fields(self, fields: String) -> Result<(String, Params<'a>), SQLError> {
+        let (filter_list, values) = self.filter.build()?;
+        let sort_list = match self.sort_list.is_empty() {
+            true => None,
+            false => Some(self.sort_list),
+        };
+        let query = T::select_join_query_builder(
+            self.table_name,
+            self.alias,
+            &self.join_list,
+            fields,
+            filter_list,
+            sort_list,
+            self.sort_type,
+            self.limit,
+        )?;
+        Ok((query, values))
+    }
+
+    /// Runs the query and returns PostgreSQL rows
+    pub async fn fetch_rows<E>(self, executor: &E) -> Result<Vec<Row>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::query(executor, QueryType::RAW(query), &values, true).await
+    }
+
+    /// Runs the query and parses rows to any type with `FromRow` (see `DPQueryable::parse_generic_type`),
+    /// e.g. a struct of joined fields, or a struct with `#[from_row(flatten)]` structs
+    pub async fn fetch_as<R, E>(self, executor: &E) -> Result<Vec<R>, SQLError>
+    where
+        R: FromRow,
+        E: PgExecutor + ?Sized,
+    {
+        self.fetch_rows(executor)
+            .await?
+            .iter()
+            .map(T::parse_generic_type::<R>)
+            .collect()
+    }
+
+    /// Runs the query and returns pairs of `T` and the joined table `J` with alias `join_alias`;
+    /// `J` is `None` if all of its fields are `NULL` (`LEFT JOIN` without a matched row)
+    ///
+    /// Fields are read by their position (see `DPQueryable::parse_type_at`), so both `T` and `J` need
+    /// `columns` (e.g. `#[derive(Table)]`), and same field names (e.g. `id`) do not conflict; a manual `J`
+    /// should implement `parse_type_at` too, otherwise it returns an error
+    pub async fn fetch_pairs<J, E>(
+        self,
+        executor: &E,
+        join_alias: &str,
+    ) -> Result<Vec<(T::RowType, Option<J::RowType>)>, SQLError>
+    where
+        J: DPQueryable<'a>,
+        E: PgExecutor + ?Sized,
+    {
+        if T::columns().is_empty() || J::columns().is_empty() {
+            return Err("No columns find for fetch pairs!".to_owned().into());
+        }
+        let alias = self
+            .alias
+            .unwrap_or(self.table_name.unwrap_or(T::table_name()));
+        let fields = format!(
+            "{}, {}",
+            T::alias_field_query_builder(alias)?,
+            J::alias_field_query_builder(join_alias)?
+        );
+        let (query, values) = self.build_fields(fields)?;
+        let offset = T::columns().len();
+        T::query(executor, QueryType::RAW(query), &values, true)
+            .await?
+            .iter()
+            .map(|row| {
+                Ok((
+                    T::parse_type_at(row, 0)?,
+                    J::parse_type_opt_at(row, offset)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Runs the query and returns all rows as `RowType`
+    pub async fn fetch_all<E>(self, executor: &E) -> Result<Vec<T::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::query_typed(executor, QueryType::RAW(query), &values, true).await
+    }
+
+    /// Runs the query and returns exactly one row as `RowType`, unless cause error
+    pub async fn fetch_one<E>(self, executor: &E) -> Result<T::RowType, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::query_one_typed(executor, QueryType::RAW(query), &values, true).await
+    }
+
+    /// Runs the query and returns maximum one row as `RowType`, unless cause error
+    pub async fn fetch_opt<E>(self, executor: &E) -> Result<Option<T::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::query_opt_typed(executor, QueryType::RAW(query), &values, true).await
+    }
+
+    /// Runs the query and returns a stream of `RowType` (see `DPQueryable::query_stream_typed`)
+    pub async fn fetch_stream<'e, E>(
+        self,
+        executor: &'e E,
+    ) -> Result<BoxStream<'e, Result<T::RowType, SQLError>>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::query_stream_typed(executor, QueryType::RAW(query), values, true).await
+    }
+
+    /// Returns the `SELECT COUNT(*)` query of the same tables, joins and filters (without sorts and limits)
+    /// and its values
+    pub fn build_count(mut self) -> Result<(String, Params<'a>), SQLError> {
+        self.sort_list.clear();
+        self.sort_type = None;
+        self.limit = None;
+        let (query, values) = self.build_fields("1".to_owned())?;
+        Ok((format!("SELECT COUNT(*) FROM ({}) AS t", query), values))
+    }
+
+    /// Returns number of rows matched by joins and filters (sorts and limits are ignored)
+    pub async fn count<E>(self, executor: &E) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build_count()?;
+        let count: i64 = T::query_one(executor, QueryType::RAW(query), &values, true)
+            .await?
+            .try_get(0)?;
+        Ok(count as u64)
+    }
+}
+
+impl<'a, T> Default for Select<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// This `struct` provides a fluent `GROUP BY` query builder; it is created by `DPQueryable::group_by_builder()`
+///
+/// Group fields come first in the result rows, then aggregates in the order they are added; `having` and
+/// `order_by` fields can be aliases of aggregates
+///
+/// ```no_run
+/// #[derive(FromRow)]
+/// struct StatusTotal {
+///     status: String,
+///     orders: i64,
+///     average: Option<f64>,
+/// }
+///
+/// let rows: Vec<StatusTotal> = Order::group_by_builder()
+///     .group("status")
+///     .aggregate(SQLAggregate::COUNT, "orders")
+///     .aggregate(SQLAggregate::AVG("total"), "average")
+///     .filter(SQLCondition::GE("created_at"), &[&since])
+///     .having(SQLCondition::GREATER("orders"), &[&10i64])
+///     .order_by(SQLOrder::desc("orders"))
+///     .fetch_as(&pool)
+///     .await?;
+/// ```
+pub struct GroupBy<'a, T> {
+    table_name: Option<&'a str>,
+    group_list: Vec<&'a str>,
+    aggregate_list: Vec<(SQLAggregate<'a>, &'a str)>,
+    filter: Filter<'a>,
+    having: Filter<'a>,
+    sort_list: Vec<SQLOrder<'a>>,
+    sort_type: Option<SQLSort>,
+    limit: Option<SQLLimit>,
+    table: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> GroupBy<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    /// Creates a `GROUP BY` query of `T::table_name()`
+    pub fn new() -> Self {
+        Self {
+            table_name: None,
+            group_list: Vec::new(),
+            aggregate_list: Vec::new(),
+            filter: Filter::default(),
+            having: Filter::default(),
+            sort_list: Vec::new(),
+            sort_type: None,
+            limit: None,
+            table: PhantomData,
+        }
+    }
+
+    /// Selects from `table_name` instead of `T::table_name()`
+    pub fn table(mut self, table_name: &'a str) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// Adds a field to `GROUP BY` (it is selected too)
+    pub fn group<F>(mut self, field: F) -> Self
+    where
+        F: Into<&'a str>,
+    {
+        self.group_list.push(field.into());
+        self
+    }
+
+    /// Selects `aggregate` as `alias` (e.g. `.aggregate(SQLAggregate::SUM("total"), "total")`)
+    pub fn aggregate(mut self, aggregate: SQLAggregate<'a>, alias: &'a str) -> Self {
+        self.aggregate_list.push((aggregate, alias));
+        self
+    }
+
+    /// Adds `condition` to `WHERE` with `AND`; `values` are the values of its `$n` params
+    pub fn filter(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.filter.push(SQLCondition::AND, condition, values);
+        self
+    }
+
+    /// Adds `condition` to `WHERE` with `OR`; `values` are the values of its `$n` params
+    pub fn or_filter(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.filter.push(SQLCondition::OR, condition, values);
+        self
+    }
+
+    /// Adds `condition` to `HAVING` with `AND`; its field can be an alias of an aggregate
+    pub fn having(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.having.push(SQLCondition::AND, condition, values);
+        self
+    }
+
+    /// Adds `condition` to `HAVING` with `OR`; its field can be an alias of an aggregate
+    pub fn or_having(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.having.push(SQLCondition::OR, condition, values);
+        self
+    }
+
+    /// Adds a sort field (a group field or an alias of an aggregate)
+    pub fn order_by<O>(mut self, order: O) -> Self
+    where
+        O: Into<SQLOrder<'a>>,
+    {
+        self.sort_list.push(order.into());
+        self
+    }
+
+    /// Sets the direction of sort fields without their own direction (`ASC` by default)
+    pub fn sort(mut self, sort_type: SQLSort) -> Self {
+        self.sort_type = Some(sort_type);
+        self
+    }
+
+    /// Returns maximum `limit` groups
+    pub fn limit(mut self, limit: i64) -> Self {
+        let offset = self.limit.map_or(0, |item| item.offset);
+        self.limit = Some(SQLLimit::new(limit, offset));
+        self
+    }
+
+    /// Skips the first `offset` groups
+    pub fn offset(mut self, offset: i64) -> Self {
+        let limit = self.limit.map_or(i64::MAX, |item| item.limit);
+        self.limit = Some(SQLLimit::new(limit, offset));
+        self
+    }
+
+    /// Returns the query and its values (`WHERE` values, then `HAVING` values)
+    pub fn build(self) -> Result<(String, Params<'a>), SQLError> {
+        let (filter_list, mut values) = self.filter.build()?;
+        let (having_list, having_values) = self.having.build()?;
+        values.extend(having_values);
+        let sort_list = match self.sort_list.is_empty() {
+            true => None,
+            false => Some(self.sort_list),
+        };
+        let query = T::group_query_builder(
+            self.table_name,
+            &self.group_list,
+            &self.aggregate_list,
+            filter_list,
+            having_list,
+            sort_list,
+            self.sort_type,
+            self.limit,
+        )?;
+        Ok((query, values))
+    }
+
+    /// Runs the query and returns PostgreSQL rows
+    pub async fn fetch_rows<E>(self, executor: &E) -> Result<Vec<Row>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::query(executor, QueryType::RAW(query), &values, true).await
+    }
+
+    /// Runs the query and parses rows to any type with `FromRow` (e.g. a struct of group fields and aggregate
+    /// aliases)
+    pub async fn fetch_as<R, E>(self, executor: &E) -> Result<Vec<R>, SQLError>
+    where
+        R: FromRow,
+        E: PgExecutor + ?Sized,
+    {
+        self.fetch_rows(executor)
+            .await?
+            .iter()
+            .map(T::parse_generic_type::<R>)
+            .collect()
+    }
+}
+
+impl<'a, T> Default for GroupBy<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// This `struct` provides a fluent `INSERT` query builder; it is created by `DPQueryable::insert_builder()`
+///
+/// ```no_run
+/// ExampleTable::insert_builder()
+///     .value("id", &id)
+///     .value(ExampleTable::NAME, &name)
+///     .on_conflict(vec!["id"], SQLConflict::UPDATE(None))
+///     .execute(&pool)
+///     .await?;
+/// ```
+pub struct Insert<'a, T> {
+    table_name: Option<&'a str>,
+    field_list: Vec<&'a str>,
+    values: Params<'a>,
+    conflict: Option<(Vec<&'a str>, SQLConflict<'a>)>,
+    returning_list: Option<Vec<&'a str>>,
+    table: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Insert<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    /// Creates an `INSERT` query to `T::table_name()`
+    pub fn new() -> Self {
+        Self {
+            table_name: None,
+            field_list: Vec::new(),
+            values: Vec::new(),
+            conflict: None,
+            returning_list: None,
+            table: PhantomData,
+        }
+    }
+
+    /// Inserts to `table_name` instead of `T::table_name()`
+    pub fn table(mut self, table_name: &'a str) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// Adds a field and its value
+    pub fn value<F>(mut self, field: F, value: &'a (dyn ToSql + Sync)) -> Self
+    where
+        F: Into<&'a str>,
+    {
+        self.field_list.push(field.into());
+        self.values.push(value);
+        self
+    }
+
+    /// Runs `action` if the row conflicts with an existing row on `conflict_list` (`INSERT ... ON CONFLICT`)
+    pub fn on_conflict<F>(mut self, conflict_list: Vec<F>, action: SQLConflict<'a>) -> Self
+    where
+        F: Into<&'a str>,
+    {
+        self.conflict = Some((conflict_list.into_iter().map(Into::into).collect(), action));
+        self
+    }
+
+    /// Returns `returning_list` fields instead of all fields in [`execute_returning`]
+    ///
+    /// [`execute_returning`]: #method.execute_returning
+    pub fn returning<F>(mut self, returning_list: Vec<F>) -> Self
+    where
+        F: Into<&'a str>,
+    {
+        self.returning_list = Some(returning_list.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns the query (without `RETURNING`) and its values
+    pub fn build(self) -> Result<(String, Params<'a>), SQLError> {
+        if self.field_list.is_empty() {
+            return Err("No insert field find!".to_owned().into());
+        }
+        let query = match self.conflict {
+            None => {
+                T::insert_query_builder(self.table_name, Some(self.field_list), self.values.len())?
+            }
+            Some((conflict_list, action)) => T::upsert_query_builder(
+                self.table_name,
+                self.field_list,
+                conflict_list,
+                action,
+                None,
+            )?,
+        };
+        Ok((query, self.values))
+    }
+
+    /// Runs the query and returns number of inserted (or updated) rows
+    pub async fn execute<E>(self, executor: &E) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::execute(executor, QueryType::RAW(query), &values, false).await
+    }
+
+    /// Runs the query and returns the inserted (or updated) rows as `RowType`
+    pub async fn execute_returning<E>(self, executor: &E) -> Result<Vec<T::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let returning = T::returning_query_builder(self.returning_list.clone())?;
+        let (query, values) = self.build()?;
+        let query = format!("{}{}", query, returning);
+        T::query_typed(executor, QueryType::RAW(query), &values, false).await
+    }
+    /// Runs the query and returns the value of `id_field` of the inserted row (e.g. a generated identity key);
+    /// `SQLConflict::NOTHING` conflicts return no row, which is an error
+    pub async fn execute_id<I, E>(self, executor: &E, id_field: &str) -> Result<I, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        for<'b> I: FromSql<'b>,
+    {
+        let id_field = SQLIdentifier::quote(id_field)?;
+        let (query, values) = self.build()?;
+        let query = format!("{} RETURNING {}", query, id_field);
+        Ok(
+            T::query_one(executor, QueryType::RAW(query), &values, false)
+                .await?
+                .try_get(0)?,
+        )
+    }
+}
+
+impl<'a, T> Default for Insert<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// This `struct` provides a fluent `UPDATE` query builder; it is created by `DPQueryable::update_builder()`
+///
+/// ```no_run
+/// ExampleTable::update_builder()
+///     .set(ExampleTable::NAME, &name)
+///     .filter(ExampleTable::ID.equal(), &[&id])
+///     .execute(&pool)
+///     .await?;
+/// ```
+pub struct Update<'a, T> {
+    table_name: Option<&'a str>,
+    update_list: Vec<&'a str>,
+    update_values: Params<'a>,
+    filter: Filter<'a>,
+    returning_list: Option<Vec<&'a str>>,
+    table: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Update<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    /// Creates an `UPDATE` query of `T::table_name()`
+    pub fn new() -> Self {
+        Self {
+            table_name: None,
+            update_list: Vec::new(),
+            update_values: Vec::new(),
+            filter: Filter::default(),
+            returning_list: None,
+            table: PhantomData,
+        }
+    }
+
+    /// Updates `table_name` instead of `T::table_name()`
+    pub fn table(mut self, table_name: &'a str) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// Sets a field to a value
+    pub fn set<F>(mut self, field: F, value: &'a (dyn ToSql + Sync)) -> Self
+    where
+        F: Into<&'a str>,
+    {
+        self.update_list.push(field.into());
+        self.update_values.push(value);
+        self
+    }
+
+    /// Adds `condition` with `AND`; `values` are the values of its `$n` params
+    pub fn filter(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.filter.push(SQLCondition::AND, condition, values);
+        self
+    }
+
+    /// Adds `condition` with `OR`; `values` are the values of its `$n` params
+    pub fn or_filter(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.filter.push(SQLCondition::OR, condition, values);
+        self
+    }
+
+    /// Returns `returning_list` fields instead of all fields in [`execute_returning`]
+    ///
+    /// [`execute_returning`]: #method.execute_returning
+    pub fn returning<F>(mut self, returning_list: Vec<F>) -> Self
+    where
+        F: Into<&'a str>,
+    {
+        self.returning_list = Some(returning_list.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns the query (without `RETURNING`) and its values
+    pub fn build(self) -> Result<(String, Params<'a>), SQLError> {
+        let (filter_list, filter_values) = self.filter.build()?;
+        let query = T::update_statement_builder(self.table_name, self.update_list, filter_list)?;
+        Ok((query, [self.update_values, filter_values].concat()))
+    }
+
+    /// Runs the query and returns number of updated rows
+    pub async fn execute<E>(self, executor: &E) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::execute(executor, QueryType::RAW(query), &values, false).await
+    }
+
+    /// Runs the query and returns the updated rows as `RowType`
+    pub async fn execute_returning<E>(self, executor: &E) -> Result<Vec<T::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let returning = T::returning_query_builder(self.returning_list.clone())?;
+        let (query, values) = self.build()?;
+        let query = format!("{}{}", query, returning);
+        T::query_typed(executor, QueryType::RAW(query), &values, false).await
+    }
+}
+
+impl<'a, T> Default for Update<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// This `struct` provides a fluent `DELETE` query builder; it is created by `DPQueryable::delete_builder()`
+///
+/// ```no_run
+/// ExampleTable::delete_builder()
+///     .filter(ExampleTable::ID.is_in(), &[&ids])
+///     .execute(&pool)
+///     .await?;
+/// ```
+pub struct Delete<'a, T> {
+    table_name: Option<&'a str>,
+    filter: Filter<'a>,
+    returning_list: Option<Vec<&'a str>>,
+    table: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Delete<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    /// Creates a `DELETE` query of `T::table_name()`
+    pub fn new() -> Self {
+        Self {
+            table_name: None,
+            filter: Filter::default(),
+            returning_list: None,
+            table: PhantomData,
+        }
+    }
+
+    /// Deletes from `table_name` instead of `T::table_name()`
+    pub fn table(mut self, table_name: &'a str) -> Self {
+        self.table_name = Some(table_name);
+        self
+    }
+
+    /// Adds `condition` with `AND`; `values` are the values of its `$n` params
+    pub fn filter(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.filter.push(SQLCondition::AND, condition, values);
+        self
+    }
+
+    /// Adds `condition` with `OR`; `values` are the values of its `$n` params
+    pub fn or_filter(
+        mut self,
+        condition: SQLCondition<'a>,
+        values: &[&'a (dyn ToSql + Sync)],
+    ) -> Self {
+        self.filter.push(SQLCondition::OR, condition, values);
+        self
+    }
+
+    /// Returns `returning_list` fields instead of all fields in [`execute_returning`]
+    ///
+    /// [`execute_returning`]: #method.execute_returning
+    pub fn returning<F>(mut self, returning_list: Vec<F>) -> Self
+    where
+        F: Into<&'a str>,
+    {
+        self.returning_list = Some(returning_list.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns the query (without `RETURNING`) and its values
+    pub fn build(self) -> Result<(String, Params<'a>), SQLError> {
+        let (filter_list, values) = self.filter.build()?;
+        let query = T::delete_query_builder(self.table_name, filter_list)?;
+        Ok((query, values))
+    }
+
+    /// Runs the query and returns number of deleted rows
+    pub async fn execute<E>(self, executor: &E) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let (query, values) = self.build()?;
+        T::execute(executor, QueryType::RAW(query), &values, false).await
+    }
+
+    /// Runs the query and returns the deleted rows as `RowType`
+    pub async fn execute_returning<E>(self, executor: &E) -> Result<Vec<T::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let returning = T::returning_query_builder(self.returning_list.clone())?;
+        let (query, values) = self.build()?;
+        let query = format!("{}{}", query, returning);
+        T::query_typed(executor, QueryType::RAW(query), &values, false).await
+    }
+}
+
+impl<'a, T> Default for Delete<'a, T>
+where
+    T: DPQueryable<'a> + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::SQLColumn;
+    use postgres_types::{FromSql, ToSql};
+    use serde::Serialize;
+
+    #[derive(Debug, FromRow, ToSql, FromSql, Serialize)]
+    struct Order {
+        id: i64,
+    }
+
+    impl DPQueryable<'_> for Order {
+        type RowType = Self;
+        fn table_name() -> &'static str {
+            "public.orders"
+        }
+    }
+
+    #[test]
+    fn fields_accept_columns() {
+        const ID: SQLColumn = SQLColumn {
+            name: "id",
+            field: "id",
+            primary_key: true,
+            skip_insert: false,
+            identity: false,
+        };
+        let (query, _) = Select::<Order>::new().fields(vec![ID]).build().unwrap();
+        assert!(query.starts_with(r#"SELECT "id" FROM "public"."orders""#));
+        let (query, _) = Select::<Order>::new().fields(vec!["id"]).build().unwrap();
+        assert!(query.starts_with(r#"SELECT "id" FROM "public"."orders""#));
+    }
+
+    #[test]
+    fn count_keeps_joins() {
+        let (query, values) = Select::<Order>::new()
+            .alias("o")
+            .join(SQLJoin::inner("public.customers", "c").on("c.id", "o.customer_id"))
+            .filter(SQLCondition::EQUAL("c.name"), &[&"a"])
+            .order_by("o.id")
+            .limit(10)
+            .build_count()
+            .unwrap();
+        assert!(query.starts_with("SELECT COUNT(*) FROM (SELECT 1 FROM "));
+        assert!(query.contains("JOIN"));
+        assert!(!query.contains("ORDER BY") && !query.contains("LIMIT"));
+        assert!(query.ends_with(") AS t"));
+        assert_eq!(values.len(), 1);
+    }
+
+    #[test]
+    fn having_follows_filter() {
+        let (query, values) = GroupBy::<Order>::new()
+            .group("customer_id")
+            .aggregate(SQLAggregate::SUM("total"), "total")
+            .aggregate(SQLAggregate::COUNT, "orders")
+            .filter(
+                SQLCondition::BETWEEN("created_at"),
+                &[&"2024-01-01", &"2025-01-01"],
+            )
+            .filter(SQLCondition::NOTNULL("paid_at"), &[])
+            .having(SQLCondition::GREATER("total"), &[&100])
+            .or_having(SQLCondition::GE("orders"), &[&10])
+            .order_by("total")
+            .build()
+            .unwrap();
+        let query = query.split_whitespace().collect::<Vec<&str>>().join(" ");
+        assert_eq!(
+            query,
+            r#"SELECT "customer_id", SUM("total") AS "total", COUNT(*) AS "orders" FROM "public"."orders" WHERE "created_at" BETWEEN $1 AND $2 AND "paid_at" IS NOT NULL GROUP BY "customer_id" HAVING SUM("total") > $3 OR COUNT(*) >= $4 ORDER BY "total" ASC"#
+        );
+        assert_eq!(values.len(), 4);
+    }
+}
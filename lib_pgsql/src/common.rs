@@ -1,15 +1,188 @@
+use bytes::BytesMut;
 use core::fmt;
+use serde::Serialize;
+use std::error::Error;
 use std::io;
+use tokio_postgres::error::{DbError, SqlState};
+use tokio_postgres::types::{to_sql_checked, Format, FromSql, IsNull, ToSql, Type};
 
 /// This `enum` can handle any error generated druing PostgreSQL operations
 ///
-/// You can use it for error handle in `Result<_, SQLError>`
+/// You can use it for error handle in `Result<_, SQLError>`; [`kind`] classifies it (e.g. to map errors to
+/// HTTP status codes), and [`constraint`] and [`column`] return details of database errors
+///
+/// ```no_run
+/// match ExampleTable::insert(&pool, None, None, &[&id, &name]).await {
+///     Err(e) if e.kind() == SQLErrorKind::UNIQUE_VIOLATION => println!("{:?} exists", e.column()),
+///     result => result?,
+/// }
+/// ```
+///
+/// [`kind`]: #method.kind
+/// [`constraint`]: #method.constraint
+/// [`column`]: #method.column
 #[derive(Debug)]
 pub enum SQLError {
     TkError(tokio_postgres::Error),
     IoError(io::Error),
     PoolError(deadpool_postgres::PoolError),
     StringError(String),
+    IdentifierError(String),
+    NotFoundError,
+    QueryNotFoundError(String),
+}
+
+/// This `enum` provides classes of `SQLError` (see `SQLError::kind`)
+///
+/// `CONNECTION_LOST` also includes pool timeouts, when there is no connection to run a query (a closed pool is
+/// `OTHER`, since it never recovers); `NOT_FOUND` is a query of exactly one row (e.g. `query_one`) without any row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum SQLErrorKind {
+    UNIQUE_VIOLATION,
+    FOREIGN_KEY_VIOLATION,
+    SERIALIZATION_FAILURE,
+    DEADLOCK,
+    CONNECTION_LOST,
+    NOT_FOUND,
+    OTHER,
+}
+
+impl SQLError {
+    /// Returns the class of this error, based on SQLSTATE code of database errors
+    pub fn kind(&self) -> SQLErrorKind {
+        match self {
+            Self::TkError(e) => Self::postgres_kind(e),
+            Self::PoolError(deadpool_postgres::PoolError::Backend(e)) => Self::postgres_kind(e),
+            Self::PoolError(deadpool_postgres::PoolError::Timeout(_)) => {
+                SQLErrorKind::CONNECTION_LOST
+            }
+            Self::NotFoundError => SQLErrorKind::NOT_FOUND,
+            _ => SQLErrorKind::OTHER,
+        }
+    }
+
+    /// Returns the class of a `tokio_postgres` error; errors without SQLSTATE code are connection errors if the
+    /// connection is closed or it is an IO error
+    fn postgres_kind(error: &tokio_postgres::Error) -> SQLErrorKind {
+        let code = match error.code() {
+            Some(code) => code.code(),
+            None if error.is_closed()
+                || error
+                    .source()
+                    .is_some_and(|source| source.is::<io::Error>()) =>
+            {
+                return SQLErrorKind::CONNECTION_LOST
+            }
+            None => return SQLErrorKind::OTHER,
+        };
+        match code {
+            "23505" => SQLErrorKind::UNIQUE_VIOLATION,
+            "23503" => SQLErrorKind::FOREIGN_KEY_VIOLATION,
+            "40001" => SQLErrorKind::SERIALIZATION_FAILURE,
+            "40P01" => SQLErrorKind::DEADLOCK,
+            // connection exceptions, and admin shutdown, crash shutdown or can not connect now
+            code if code.starts_with("08") || matches!(code, "57P01" | "57P02" | "57P03") => {
+                SQLErrorKind::CONNECTION_LOST
+            }
+            _ => SQLErrorKind::OTHER,
+        }
+    }
+
+    /// Returns the error of PostgreSQL server, if it is a database error
+    pub fn db_error(&self) -> Option<&DbError> {
+        match self {
+            Self::TkError(e) => e.as_db_error(),
+            Self::PoolError(deadpool_postgres::PoolError::Backend(e)) => e.as_db_error(),
+            _ => None,
+        }
+    }
+
+    /// Returns SQLSTATE code of a database error (e.g. `23505` for unique violation)
+    pub fn code(&self) -> Option<&SqlState> {
+        self.db_error().map(|e| e.code())
+    }
+
+    /// Returns the name of the violated constraint of a database error (e.g. `example_table_pkey`)
+    pub fn constraint(&self) -> Option<&str> {
+        self.db_error().and_then(|e| e.constraint())
+    }
+
+    /// Returns the table of a database error
+    pub fn table(&self) -> Option<&str> {
+        self.db_error().and_then(|e| e.table())
+    }
+
+    /// Returns the offending column of a database error; for unique and foreign key violations, it is read from
+    /// the detail (`Key (name)=(value) ...`), which is the list of key columns (e.g. `a, b`)
+    pub fn column(&self) -> Option<&str> {
+        let error = self.db_error()?;
+        error.column().or_else(|| {
+            error
+                .detail()?
+                .strip_prefix("Key (")?
+                .split_once(")=(")
+                .map(|(column, _)| column)
+        })
+    }
+
+    /// Returns `true` if it is a `UNIQUE` or primary key violation
+    pub fn is_unique_violation(&self) -> bool {
+        self.kind() == SQLErrorKind::UNIQUE_VIOLATION
+    }
+
+    /// Returns `true` if it is a foreign key violation
+    pub fn is_foreign_key_violation(&self) -> bool {
+        self.kind() == SQLErrorKind::FOREIGN_KEY_VIOLATION
+    }
+
+    /// Returns `true` if it is a serialization failure of a transaction (SQLSTATE `40001`)
+    pub fn is_serialization_failure(&self) -> bool {
+        self.kind() == SQLErrorKind::SERIALIZATION_FAILURE
+    }
+
+    /// Returns `true` if the transaction is aborted by a deadlock (SQLSTATE `40P01`)
+    pub fn is_deadlock(&self) -> bool {
+        self.kind() == SQLErrorKind::DEADLOCK
+    }
+
+    /// Returns `true` if the connection is lost or not available
+    pub fn is_connection_lost(&self) -> bool {
+        self.kind() == SQLErrorKind::CONNECTION_LOST
+    }
+
+    /// Returns `true` if a query of exactly one row did not find any row
+    pub fn is_not_found(&self) -> bool {
+        self.kind() == SQLErrorKind::NOT_FOUND
+    }
+}
+
+impl fmt::Display for SQLError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TkError(e) => match e.as_db_error() {
+                Some(db_error) => write!(f, "{}", db_error),
+                None => write!(f, "{}", e),
+            },
+            Self::IoError(e) => write!(f, "{}", e),
+            Self::PoolError(e) => write!(f, "{}", e),
+            Self::StringError(e) => write!(f, "{}", e),
+            Self::IdentifierError(e) => write!(f, "Invalid identifier {}!", e),
+            Self::NotFoundError => write!(f, "No row find!"),
+            Self::QueryNotFoundError(e) => write!(f, "No query {} find in query lib!", e),
+        }
+    }
+}
+
+impl Error for SQLError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TkError(e) => Some(e),
+            Self::IoError(e) => Some(e),
+            Self::PoolError(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 /// Convert `tokio_postgre` Error to `SQLError`
@@ -39,6 +212,92 @@ impl From<String> for SQLError {
     }
 }
 
+/// This `struct` validates and quotes PostgreSQL identifiers (table and column names)
+///
+/// Each part of a (schema-qualified) identifier should be a plain identifier (letters, digits, `_` and `$`, not
+/// starting with a digit or `$`), whose ASCII letters are folded to lower case like PostgreSQL does (e.g. `ÀB` is
+/// `"Àb"`), or an already quoted identifier (`"Name"`), which is kept as it is; other identifiers are rejected
+/// with `SQLError::IdentifierError`
+///
+/// ```no_run
+/// assert_eq!(SQLIdentifier::quote("public.Example_Table")?, r#""public"."example_table""#);
+/// assert_eq!(SQLIdentifier::quote_field("full_name AS name")?, r#""full_name" AS "name""#);
+/// ```
+pub struct SQLIdentifier;
+
+impl SQLIdentifier {
+    /// Quotes an identifier (`table`, `schema.table` or `schema.table.column`)
+    pub fn quote(identifier: &str) -> Result<String, SQLError> {
+        let error = || SQLError::IdentifierError(identifier.to_owned());
+        let mut parts: Vec<String> = Vec::new();
+        let mut chars = identifier.trim().chars().peekable();
+        loop {
+            let mut part = String::new();
+            if chars.peek() == Some(&'"') {
+                chars.next();
+                loop {
+                    match chars.next() {
+                        None => return Err(error()),
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            part.push_str("\"\"");
+                        }
+                        Some('"') => break,
+                        Some(c) => part.push(c),
+                    }
+                }
+            } else {
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+                {
+                    part.push(c.to_ascii_lowercase());
+                }
+                if !part.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+                    return Err(error());
+                }
+            }
+            if part.is_empty() {
+                return Err(error());
+            }
+            parts.push(format!("\"{}\"", part));
+            match chars.next() {
+                None => break,
+                Some('.') if parts.len() < 3 => continue,
+                Some(_) => return Err(error()),
+            }
+        }
+        Ok(parts.join("."))
+    }
+
+    /// Returns the unquoted name of a single identifier (e.g. `ID` is `id`, and `"Id"` is `Id`), for functions
+    /// that take names as text (e.g. `pg_get_serial_sequence`)
+    pub fn name(identifier: &str) -> Result<String, SQLError> {
+        let quoted = Self::quote(identifier)?;
+        let name = &quoted[1..quoted.len() - 1];
+        match name.replace("\"\"", "").contains('"') {
+            true => Err(SQLError::IdentifierError(identifier.to_owned())),
+            false => Ok(name.replace("\"\"", "\"")),
+        }
+    }
+
+    /// Quotes a field of `SELECT` or `RETURNING` lists; it can also be `*`, `table.*` or `field AS alias`
+    pub fn quote_field(field: &str) -> Result<String, SQLError> {
+        let field = field.trim();
+        if field == "*" {
+            return Ok(field.to_owned());
+        }
+        if let Some(table) = field.strip_suffix(".*") {
+            return Ok(format!("{}.*", Self::quote(table)?));
+        }
+        let items: Vec<&str> = field.split_whitespace().collect();
+        match items[..] {
+            [name, keyword, alias] if keyword.eq_ignore_ascii_case("AS") => {
+                Ok(format!("{} AS {}", Self::quote(name)?, Self::quote(alias)?))
+            }
+            _ => Self::quote(field),
+        }
+    }
+}
+
 /// This `enum` provides different type input as query
 ///
 /// `RAW("SQL query")` is string query type
@@ -69,7 +328,569 @@ impl fmt::Display for SQLSort {
     }
 }
 
+/// This `enum` provides position of `NULL` values when sorting in SQL queries
+#[derive(Debug, Clone, Copy)]
+pub enum SQLNulls {
+    FIRST,
+    LAST,
+}
+
+impl fmt::Display for SQLNulls {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FIRST => write!(f, "NULLS FIRST"),
+            Self::LAST => write!(f, "NULLS LAST"),
+        }
+    }
+}
+
+/// This `struct` provides sorting of one field in SQL queries
+///
+/// `SQLOrder::desc("priority").nulls_last()` means `priority DESC NULLS LAST`; a field without `sort`
+/// (e.g. `"created_at".into()`) uses the `sort_type` passed next to the sort list
+#[derive(Debug, Clone, Copy)]
+pub struct SQLOrder<'a> {
+    pub field: &'a str,
+    pub sort: Option<SQLSort>,
+    pub nulls: Option<SQLNulls>,
+}
+
+impl<'a> SQLOrder<'a> {
+    /// Sorts `field` in ascending order
+    pub fn asc(field: &'a str) -> Self {
+        Self {
+            field,
+            sort: Some(SQLSort::ASC),
+            nulls: None,
+        }
+    }
+
+    /// Sorts `field` in descending order
+    pub fn desc(field: &'a str) -> Self {
+        Self {
+            field,
+            sort: Some(SQLSort::DESC),
+            nulls: None,
+        }
+    }
+
+    /// Puts `NULL` values before other values
+    pub fn nulls_first(self) -> Self {
+        Self {
+            nulls: Some(SQLNulls::FIRST),
+            ..self
+        }
+    }
+
+    /// Puts `NULL` values after other values
+    pub fn nulls_last(self) -> Self {
+        Self {
+            nulls: Some(SQLNulls::LAST),
+            ..self
+        }
+    }
+}
+
+impl<'a> From<&'a str> for SQLOrder<'a> {
+    fn from(field: &'a str) -> Self {
+        Self {
+            field,
+            sort: None,
+            nulls: None,
+        }
+    }
+}
+
+impl<'a> SQLOrder<'a> {
+    /// Returns this order for SQL queries, with a quoted field (see `SQLIdentifier`)
+    pub fn to_query(&self) -> Result<String, SQLError> {
+        let field = SQLIdentifier::quote(self.field)?;
+        Ok(SQLOrder {
+            field: &field,
+            ..*self
+        }
+        .to_string())
+    }
+}
+
+impl<'a> fmt::Display for SQLOrder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.field)?;
+        if let Some(sort) = self.sort {
+            write!(f, " {}", sort)?;
+        }
+        if let Some(nulls) = self.nulls {
+            write!(f, " {}", nulls)?;
+        }
+        Ok(())
+    }
+}
+
+/// This `enum` provides aggregate functions of SQL queries; `COUNT` is `COUNT(*)`
+///
+/// `AVG` is always returned as `double precision` (`AVG(field)::float8`), since `AVG` of integers is `numeric`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum SQLAggregate<'a> {
+    COUNT,
+    COUNT_DISTINCT(&'a str),
+    SUM(&'a str),
+    AVG(&'a str),
+    MIN(&'a str),
+    MAX(&'a str),
+}
+
+impl<'a> SQLAggregate<'a> {
+    /// Returns the default alias of this aggregate (e.g. `count_distinct`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::COUNT => "count",
+            Self::COUNT_DISTINCT(_) => "count_distinct",
+            Self::SUM(_) => "sum",
+            Self::AVG(_) => "avg",
+            Self::MIN(_) => "min",
+            Self::MAX(_) => "max",
+        }
+    }
+
+    /// Returns this aggregate for SQL queries, with a quoted field (see `SQLIdentifier`)
+    pub fn to_query(&self) -> Result<String, SQLError> {
+        self.render(&SQLIdentifier::quote)
+    }
+
+    fn render(&self, quote: &dyn Fn(&str) -> Result<String, SQLError>) -> Result<String, SQLError> {
+        Ok(match self {
+            Self::COUNT => "COUNT(*)".to_owned(),
+            Self::COUNT_DISTINCT(field) => format!("COUNT(DISTINCT {})", quote(field)?),
+            Self::SUM(field) => format!("SUM({})", quote(field)?),
+            Self::AVG(field) => format!("AVG({})::float8", quote(field)?),
+            Self::MIN(field) => format!("MIN({})", quote(field)?),
+            Self::MAX(field) => format!("MAX({})", quote(field)?),
+        })
+    }
+}
+
+impl<'a> fmt::Display for SQLAggregate<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let query = self
+            .render(&|field| Ok(field.to_owned()))
+            .map_err(|_| fmt::Error)?;
+        write!(f, "{}", query)
+    }
+}
+
+/// This `enum` provides kind of `JOIN` in SQL queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SQLJoinType {
+    INNER,
+    LEFT,
+}
+
+impl fmt::Display for SQLJoinType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::INNER => write!(f, "INNER JOIN"),
+            Self::LEFT => write!(f, "LEFT JOIN"),
+        }
+    }
+}
+
+/// This `struct` provides a `JOIN` of SQL queries; `on` pairs of fields are compared with `=` and joined with `AND`
+///
+/// ```no_run
+/// let join = SQLJoin::left("public.customers", "c").on("c.id", "o.customer_id");
+/// // LEFT JOIN "public"."customers" AS "c" ON "c"."id" = "o"."customer_id"
+/// ```
+#[derive(Debug, Clone)]
+pub struct SQLJoin<'a> {
+    pub join_type: SQLJoinType,
+    pub table_name: &'a str,
+    pub alias: &'a str,
+    pub on: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> SQLJoin<'a> {
+    /// Returns `INNER JOIN table_name AS alias`
+    pub fn inner(table_name: &'a str, alias: &'a str) -> Self {
+        Self {
+            join_type: SQLJoinType::INNER,
+            table_name,
+            alias,
+            on: Vec::new(),
+        }
+    }
+
+    /// Returns `LEFT JOIN table_name AS alias`
+    pub fn left(table_name: &'a str, alias: &'a str) -> Self {
+        Self {
+            join_type: SQLJoinType::LEFT,
+            ..Self::inner(table_name, alias)
+        }
+    }
+
+    /// Adds `field = other_field` to the `ON` conditions
+    pub fn on(mut self, field: &'a str, other_field: &'a str) -> Self {
+        self.on.push((field, other_field));
+        self
+    }
+
+    /// Returns this join for SQL queries, with quoted identifiers (see `SQLIdentifier`)
+    pub fn to_query(&self) -> Result<String, SQLError> {
+        if self.on.is_empty() {
+            return Err("No join condition find!".to_owned().into());
+        }
+        let on = self
+            .on
+            .iter()
+            .map(|(field, other_field)| {
+                Ok(format!(
+                    "{} = {}",
+                    SQLIdentifier::quote(field)?,
+                    SQLIdentifier::quote(other_field)?
+                ))
+            })
+            .collect::<Result<Vec<String>, SQLError>>()?;
+        Ok(format!(
+            " {} {} AS {} ON {} ",
+            self.join_type,
+            SQLIdentifier::quote(self.table_name)?,
+            SQLIdentifier::quote(self.alias)?,
+            on.join(" AND ")
+        ))
+    }
+}
+
+/// This `enum` provides the action of `INSERT ... ON CONFLICT` queries
+///
+/// `NOTHING` means `DO NOTHING`
+///
+/// `UPDATE(None)` means `DO UPDATE SET` of all inserted fields except conflict fields to their `EXCLUDED` values;
+/// `UPDATE(Some(vec!["name"]))` only updates `name = EXCLUDED.name`
+#[derive(Debug, Clone)]
+pub enum SQLConflict<'a> {
+    NOTHING,
+    UPDATE(Option<Vec<&'a str>>),
+}
+
+/// This `struct` describes one column of a table (generated by `#[derive(Table)]`)
+///
+/// `name` is the PostgreSQL column and `field` is the Rust field; `skip_insert` columns are not in default `INSERT` fields
+///
+/// `identity` columns are `GENERATED ALWAYS AS IDENTITY`; `INSERT` queries with them in the field list (e.g. with ids
+/// reserved by `DPQueryable::next_ids`) add `OVERRIDING SYSTEM VALUE`
+///
+/// `#[derive(Table)]` also generates a constant per column (e.g. `ExampleTable::NAME`), which can be used instead of
+/// raw field names, so a typo is a compile error
+///
+/// ```no_run
+/// ExampleTable::select_typed(&pool, None, Some(vec![ExampleTable::NAME.like()]), &[&"a%"], Some(vec![ExampleTable::ID.desc()]), None, None).await?;
+/// ExampleTable::update(&pool, None, SQLColumn::list(&[ExampleTable::NAME]), &[&name], Some(vec![ExampleTable::ID.equal()]), &[&id]).await?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SQLColumn {
+    pub name: &'static str,
+    pub field: &'static str,
+    pub primary_key: bool,
+    pub skip_insert: bool,
+    pub identity: bool,
+}
+
+impl SQLColumn {
+    /// Like [`select_field`], but the column is qualified with a table alias (`"alias"."name" AS "field"`)
+    ///
+    /// [`select_field`]: #method.select_field
+    pub fn select_field_of(&self, alias: &str) -> Result<String, SQLError> {
+        Ok(format!(
+            "{}.{} AS \"{}\"",
+            SQLIdentifier::quote(alias)?,
+            SQLIdentifier::quote(self.name)?,
+            self.field
+        ))
+    }
+
+    /// Returns the quoted column for `SELECT` queries; renamed columns are selected as `name AS "field"`
+    pub fn select_field(&self) -> Result<String, SQLError> {
+        let name = SQLIdentifier::quote(self.name)?;
+        match self.name == self.field {
+            true => Ok(name),
+            false => Ok(format!("{} AS \"{}\"", name, self.field)),
+        }
+    }
+
+    /// Returns names of a list of columns (e.g. for `field_list` or `update_list`)
+    pub fn list(columns: &[SQLColumn]) -> Vec<&'static str> {
+        columns.iter().map(|column| column.name).collect()
+    }
+
+    /// Returns `SQLCondition::EQUAL` of this column (`column = $1`)
+    pub fn equal(&self) -> SQLCondition<'static> {
+        SQLCondition::EQUAL(self.name)
+    }
+
+    /// Returns `SQLCondition::NEQ` of this column (`column <> $1`)
+    pub fn neq(&self) -> SQLCondition<'static> {
+        SQLCondition::NEQ(self.name)
+    }
+
+    /// Returns `SQLCondition::LESS` of this column (`column < $1`)
+    pub fn less(&self) -> SQLCondition<'static> {
+        SQLCondition::LESS(self.name)
+    }
+
+    /// Returns `SQLCondition::LE` of this column (`column <= $1`)
+    pub fn le(&self) -> SQLCondition<'static> {
+        SQLCondition::LE(self.name)
+    }
+
+    /// Returns `SQLCondition::GREATER` of this column (`column > $1`)
+    pub fn greater(&self) -> SQLCondition<'static> {
+        SQLCondition::GREATER(self.name)
+    }
+
+    /// Returns `SQLCondition::GE` of this column (`column >= $1`)
+    pub fn ge(&self) -> SQLCondition<'static> {
+        SQLCondition::GE(self.name)
+    }
+
+    /// Returns `SQLCondition::IN` of this column (`column = ANY($1)`)
+    pub fn is_in(&self) -> SQLCondition<'static> {
+        SQLCondition::IN(self.name)
+    }
+
+    /// Returns `SQLCondition::NOTIN` of this column (`column <> ALL($1)`)
+    pub fn not_in(&self) -> SQLCondition<'static> {
+        SQLCondition::NOTIN(self.name)
+    }
+
+    /// Returns `SQLCondition::LIKE` of this column (`column LIKE $1`)
+    pub fn like(&self) -> SQLCondition<'static> {
+        SQLCondition::LIKE(self.name)
+    }
+
+    /// Returns `SQLCondition::ILIKE` of this column (`column ILIKE $1`)
+    pub fn ilike(&self) -> SQLCondition<'static> {
+        SQLCondition::ILIKE(self.name)
+    }
+
+    /// Returns `SQLCondition::ISNULL` of this column (`column IS NULL`)
+    pub fn is_null(&self) -> SQLCondition<'static> {
+        SQLCondition::ISNULL(self.name)
+    }
+
+    /// Returns `SQLCondition::NOTNULL` of this column (`column IS NOT NULL`)
+    pub fn not_null(&self) -> SQLCondition<'static> {
+        SQLCondition::NOTNULL(self.name)
+    }
+
+    /// Returns `SQLCondition::BETWEEN` of this column (`column BETWEEN $1 AND $2`)
+    pub fn between(&self) -> SQLCondition<'static> {
+        SQLCondition::BETWEEN(self.name)
+    }
+
+    /// Returns `SQLOrder` of this column in ascending order
+    pub fn asc(&self) -> SQLOrder<'static> {
+        SQLOrder::asc(self.name)
+    }
+
+    /// Returns `SQLOrder` of this column in descending order
+    pub fn desc(&self) -> SQLOrder<'static> {
+        SQLOrder::desc(self.name)
+    }
+}
+
+impl From<SQLColumn> for &str {
+    fn from(column: SQLColumn) -> Self {
+        column.name
+    }
+}
+
+impl From<SQLColumn> for SQLOrder<'static> {
+    fn from(column: SQLColumn) -> Self {
+        Self::from(column.name)
+    }
+}
+
+/// This `enum` provides the output format of exported rows
+///
+/// `CSV` writes the output of `COPY ... TO STDOUT (FORMAT csv, HEADER)`
+///
+/// `JSON` writes one serialized `RowType` per line (JSON Lines), or one `row_to_json` object of the selected fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SQLFormat {
+    CSV,
+    JSON,
+}
+
+/// This `struct` provides `LIMIT` and `OFFSET` for SQL queries
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SQLLimit {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl SQLLimit {
+    /// Returns `limit` rows after skipping `offset` rows
+    pub fn new(limit: i64, offset: i64) -> Self {
+        Self { limit, offset }
+    }
+
+    /// Returns page number `page` (starting from `0`) with `page_size` rows per page
+    pub fn page(page: i64, page_size: i64) -> Self {
+        Self {
+            limit: page_size,
+            offset: page * page_size,
+        }
+    }
+}
+
+impl fmt::Display for SQLLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, " LIMIT {} OFFSET {} ", self.limit, self.offset)
+    }
+}
+
+/// This `struct` provides one page of rows and total number of rows matched by the query
+#[derive(Debug, Serialize)]
+pub struct SQLPage<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// This `struct` provides an opaque cursor that points after the last row of a keyset page
+///
+/// It holds the values of the sort columns of that row and can be sent to clients as a string
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SQLCursor(pub String);
+
+impl SQLCursor {
+    /// Encodes the text values of sort columns to a cursor
+    pub fn encode(values: &[Option<String>]) -> Self {
+        let mut raw = String::new();
+        for value in values {
+            match value {
+                None => raw.push('N'),
+                Some(value) => raw.push_str(&format!("V{}:{}", value.len(), value)),
+            }
+        }
+        Self(raw.bytes().map(|byte| format!("{:02x}", byte)).collect())
+    }
+
+    /// Decodes the text values of sort columns from a cursor
+    pub fn decode(&self) -> Result<Vec<Option<String>>, SQLError> {
+        let invalid = || SQLError::StringError(format!("Invalid cursor {}!", self.0));
+        if !self.0.len().is_multiple_of(2) {
+            return Err(invalid());
+        }
+        let bytes = self
+            .0
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let mut values = Vec::new();
+        let mut rest = raw.as_str();
+        while !rest.is_empty() {
+            if let Some(next) = rest.strip_prefix('N') {
+                values.push(None);
+                rest = next;
+                continue;
+            }
+            let (len, next) = rest
+                .strip_prefix('V')
+                .and_then(|next| next.split_once(':'))
+                .ok_or_else(invalid)?;
+            let len: usize = len.parse().map_err(|_| invalid())?;
+            let value = next.get(..len).ok_or_else(invalid)?;
+            values.push(Some(value.to_owned()));
+            rest = &next[len..];
+        }
+        Ok(values)
+    }
+}
+
+impl From<String> for SQLCursor {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for SQLCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// This `struct` provides one keyset page of rows and the cursor of the next page (`None` on the last page)
+#[derive(Debug, Serialize)]
+pub struct SQLKeysetPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<SQLCursor>,
+}
+
+/// This `struct` sends a value as text, so PostgreSQL converts it to the type of the compared column
+#[derive(Debug)]
+pub struct SQLTextValue(pub Option<String>);
+
+impl ToSql for SQLTextValue {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match &self.0 {
+            None => Ok(IsNull::Yes),
+            Some(value) => {
+                out.extend_from_slice(value.as_bytes());
+                Ok(IsNull::No)
+            }
+        }
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    fn encode_format(&self, _ty: &Type) -> Format {
+        Format::Text
+    }
+
+    to_sql_checked!();
+}
+
+/// This `struct` reads only whether a PostgreSQL value of any type is `NULL`
+pub struct SQLIsNull(pub bool);
+
+impl<'a> FromSql<'a> for SQLIsNull {
+    fn from_sql(_ty: &Type, _raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Self(false))
+    }
+
+    fn from_sql_null(_ty: &Type) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Self(true))
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+}
+
 /// This `enum` provides condition for SQL queries. `SQLCondition::EQUAL("id")` means `id = $1`
+///
+/// `IN("id")` means `id = ANY($1)` and `NOTIN("id")` means `id <> ALL($1)`, so their value should be a `Vec` or slice
+///
+/// `ISNULL("id")` and `NOTNULL("id")` take no value; `BETWEEN("id")` means `id BETWEEN $1 AND $2` and takes two values
+///
+/// `GROUP(vec![...])` wraps a list of conditions in parentheses and `NOT(vec![...])` negates it, so
+/// `[EQUAL("a"), AND, GROUP(vec![EQUAL("b"), OR, EQUAL("c")])]` means `a = $1 AND (b = $2 OR c = $3)`
+#[derive(Debug, Clone)]
 pub enum SQLCondition<'a> {
     EQUAL(&'a str),
     NEQ(&'a str),
@@ -77,21 +898,200 @@ pub enum SQLCondition<'a> {
     LE(&'a str),
     GREATER(&'a str),
     GE(&'a str),
+    IN(&'a str),
+    NOTIN(&'a str),
+    LIKE(&'a str),
+    ILIKE(&'a str),
+    ISNULL(&'a str),
+    NOTNULL(&'a str),
+    BETWEEN(&'a str),
+    GROUP(Vec<SQLCondition<'a>>),
+    NOT(Vec<SQLCondition<'a>>),
     AND,
     OR,
 }
 
+impl<'a> SQLCondition<'a> {
+    /// Number of values (`$n` params) that this condition takes, including nested conditions
+    pub fn param_count(&self) -> usize {
+        match self {
+            Self::AND | Self::OR | Self::ISNULL(_) | Self::NOTNULL(_) => 0,
+            Self::BETWEEN(_) => 2,
+            Self::GROUP(items) | Self::NOT(items) => {
+                items.iter().map(|item| item.param_count()).sum()
+            }
+            _ => 1,
+        }
+    }
+
+    /// Generates a `GROUP` that joins all conditions with `AND`
+    pub fn all(conditions: Vec<SQLCondition<'a>>) -> Self {
+        Self::GROUP(Self::join(conditions, || Self::AND))
+    }
+
+    /// Generates a `GROUP` that joins all conditions with `OR`
+    pub fn any(conditions: Vec<SQLCondition<'a>>) -> Self {
+        Self::GROUP(Self::join(conditions, || Self::OR))
+    }
+
+    fn join(conditions: Vec<SQLCondition<'a>>, separator: fn() -> Self) -> Vec<Self> {
+        let mut items = Vec::with_capacity(conditions.len() * 2);
+        for condition in conditions {
+            if !items.is_empty() {
+                items.push(separator());
+            }
+            items.push(condition);
+        }
+        items
+    }
+
+    /// Returns this condition for SQL queries, with quoted fields (see `SQLIdentifier`)
+    pub fn to_query(&self) -> Result<String, SQLError> {
+        self.render(&SQLIdentifier::quote)
+    }
+
+    /// Like [`to_query`], but fields are rendered by `quote` (e.g. aggregate aliases of `HAVING`)
+    ///
+    /// [`to_query`]: #method.to_query
+    pub(crate) fn render(
+        &self,
+        quote: &dyn Fn(&str) -> Result<String, SQLError>,
+    ) -> Result<String, SQLError> {
+        Ok(match self {
+            Self::EQUAL(id) => format!(" {} = ##ID## ", quote(id)?),
+            Self::NEQ(id) => format!(" {} <> ##ID## ", quote(id)?),
+            Self::LESS(id) => format!(" {} < ##ID## ", quote(id)?),
+            Self::LE(id) => format!(" {} <= ##ID## ", quote(id)?),
+            Self::GREATER(id) => format!(" {} > ##ID## ", quote(id)?),
+            Self::GE(id) => format!(" {} >= ##ID## ", quote(id)?),
+            Self::IN(id) => format!(" {} = ANY(##ID##) ", quote(id)?),
+            Self::NOTIN(id) => format!(" {} <> ALL(##ID##) ", quote(id)?),
+            Self::LIKE(id) => format!(" {} LIKE ##ID## ", quote(id)?),
+            Self::ILIKE(id) => format!(" {} ILIKE ##ID## ", quote(id)?),
+            Self::ISNULL(id) => format!(" {} IS NULL ", quote(id)?),
+            Self::NOTNULL(id) => format!(" {} IS NOT NULL ", quote(id)?),
+            Self::BETWEEN(id) => format!(" {} BETWEEN ##ID## AND ##ID## ", quote(id)?),
+            Self::GROUP(items) | Self::NOT(items) if items.is_empty() => {
+                return Err("Empty condition group!".to_owned().into())
+            }
+            Self::GROUP(items) => format!(" ({}) ", Self::concat(items, quote)?),
+            Self::NOT(items) => format!(" NOT ({}) ", Self::concat(items, quote)?),
+            Self::AND => " AND ".to_owned(),
+            Self::OR => " OR ".to_owned(),
+        })
+    }
+
+    fn concat(
+        items: &[SQLCondition<'a>],
+        quote: &dyn Fn(&str) -> Result<String, SQLError>,
+    ) -> Result<String, SQLError> {
+        items.iter().map(|item| item.render(quote)).collect()
+    }
+}
+
 impl<'a> fmt::Display for SQLCondition<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::EQUAL(id) => write!(f, " {} = ##ID## ", id),
-            Self::NEQ(id) => write!(f, " {} <> ##ID## ", id),
-            Self::LESS(id) => write!(f, " {} < ##ID## ", id),
-            Self::LE(id) => write!(f, " {} <= ##ID## ", id),
-            Self::GREATER(id) => write!(f, " {} > ##ID## ", id),
-            Self::GE(id) => write!(f, " {} >= ##ID## ", id),
-            Self::AND => write!(f, " AND "),
-            Self::OR => write!(f, " OR "),
+        let query = self
+            .render(&|id| Ok(id.to_owned()))
+            .map_err(|_| fmt::Error)?;
+        write!(f, "{}", query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trip() {
+        let values = vec![
+            Some("10".to_owned()),
+            None,
+            Some("".to_owned()),
+            Some("a:V3:N".to_owned()),
+            Some("Ünïcode ✓".to_owned()),
+        ];
+        let cursor = SQLCursor::encode(&values);
+        assert!(cursor.0.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(cursor.decode().unwrap(), values);
+        assert_eq!(SQLCursor::encode(&[]).decode().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn cursor_malformed() {
+        let raw = |text: &str| SQLCursor(text.bytes().map(|b| format!("{:02x}", b)).collect());
+        for cursor in [
+            SQLCursor("abc".to_owned()),
+            SQLCursor("zz".to_owned()),
+            SQLCursor("ff".to_owned()),
+            raw("X"),
+            raw("V"),
+            raw("V3"),
+            raw("V3:ab"),
+            raw("Vx:abc"),
+            raw("V1:a:"),
+        ] {
+            assert!(
+                matches!(cursor.decode(), Err(SQLError::StringError(_))),
+                "{:?}",
+                cursor
+            );
+        }
+    }
+
+    #[test]
+    fn empty_condition_group() {
+        assert!(SQLCondition::GROUP(vec![]).to_query().is_err());
+        assert!(SQLCondition::NOT(vec![]).to_query().is_err());
+        assert_eq!(
+            SQLCondition::GROUP(vec![SQLCondition::EQUAL("a")])
+                .to_query()
+                .unwrap(),
+            r#" ( "a" = ##ID## ) "#
+        );
+    }
+
+    #[test]
+    fn identifier_quote() {
+        assert_eq!(SQLIdentifier::quote("Users").unwrap(), r#""users""#);
+        assert_eq!(
+            SQLIdentifier::quote(" Public.Users.ID ").unwrap(),
+            r#""public"."users"."id""#
+        );
+        assert_eq!(
+            SQLIdentifier::quote(r#"public."My ""Users""""#).unwrap(),
+            r#""public"."My ""Users""""#
+        );
+        assert_eq!(SQLIdentifier::quote("_tmp$1").unwrap(), r#""_tmp$1""#);
+        assert_eq!(SQLIdentifier::quote("ÀB").unwrap(), r#""Àb""#);
+        assert_eq!(SQLIdentifier::name(r#""My ""Id""""#).unwrap(), r#"My "Id""#);
+    }
+
+    #[test]
+    fn identifier_quote_rejects() {
+        for identifier in [
+            "",
+            r#"a"; DROP TABLE users; --"#,
+            "a; DROP TABLE users",
+            "a.b.c.d",
+            r#""abc"#,
+            r#"a."b"#,
+            "1abc",
+            "a.",
+            ".a",
+            "a b",
+            "a--b",
+            r#""""#,
+        ] {
+            assert!(
+                matches!(
+                    SQLIdentifier::quote(identifier),
+                    Err(SQLError::IdentifierError(_))
+                ),
+                "{}",
+                identifier
+            );
         }
+        assert!(SQLIdentifier::name("public.users").is_err());
     }
 }
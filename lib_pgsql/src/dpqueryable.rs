@@ -1,23 +1,43 @@
-use crate::common::{QueryType, SQLCondition, SQLError, SQLSort};
-use crate::pool::PgPools;
+use crate::builder::{Delete, GroupBy, Insert, Select, Update};
+use crate::common::{
+    QueryType, SQLAggregate, SQLColumn, SQLCondition, SQLConflict, SQLCursor, SQLError, SQLFormat,
+    SQLIdentifier, SQLIsNull, SQLJoin, SQLKeysetPage, SQLLimit, SQLOrder, SQLPage, SQLSort,
+    SQLTextValue,
+};
+use crate::executor::PgExecutor;
+use crate::transaction::PgTransaction;
 use async_trait::async_trait;
 use core::iter::IntoIterator;
 use core::marker::Sync;
-use deadpool_postgres::Client;
-use futures_util::{pin_mut, TryStreamExt};
+use futures_util::{
+    pin_mut,
+    stream::{self, BoxStream},
+    Stream, StreamExt, TryStreamExt,
+};
 use log::{self, debug};
 use num::One;
 use postgres_from_row::FromRow;
 use serde::Serialize;
 use std::fs::read_to_string;
 use std::ops::Add;
-use tokio_postgres::Statement;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio_postgres::{
-    types::{FromSql, ToSql},
-    Row, RowStream,
+    binary_copy::BinaryCopyInWriter,
+    types::{BorrowToSql, FromSql, ToSql, Type},
+    Row, RowStream, SimpleQueryMessage,
 };
+use tokio_postgres::{Client, Statement};
+
+/// Maximum number of `$n` params that PostgreSQL accepts in one query
+const MAX_QUERY_PARAMS: usize = 65535;
+
+/// Counter of declared cursors, used to give each cursor a unique name
+static CURSOR_COUNT: AtomicU64 = AtomicU64::new(0);
 
 /// This is an async trait that can implement PostgreSQL operation for a Rust struct
+///
+/// Every function takes a `PgExecutor` (`PgPools`, `PgPoolClient`, `PgTransaction` or `PgClient`) to run on
 #[async_trait]
 pub trait DPQueryable<'a> {
     /// This should be `Self` for each struct in `impl` section
@@ -45,6 +65,145 @@ pub trait DPQueryable<'a> {
         ""
     }
 
+    /// Set primary key fields of PostgreSQL table for each struct; by default, [`columns`] with `primary_key`
+    ///
+    /// [`find_by_id`] and [`delete_by_id`] only need it, but [`key_values`], [`update_entity`] and [`save`] also need
+    /// [`columns`] and [`row_values`] (generated by `#[derive(Table)]`), otherwise they return an error
+    ///
+    /// ```
+    /// fn primary_key() -> Vec<&'static str> {
+    ///     vec!["id"]
+    /// }
+    ///```
+    ///
+    /// [`columns`]: #method.columns
+    /// [`row_values`]: #method.row_values
+    /// [`key_values`]: #method.key_values
+    /// [`find_by_id`]: #method.find_by_id
+    /// [`delete_by_id`]: #method.delete_by_id
+    /// [`update_entity`]: #method.update_entity
+    /// [`save`]: #method.save
+    fn primary_key() -> Vec<&'static str> {
+        Self::columns()
+            .iter()
+            .filter(|column| column.primary_key)
+            .map(|column| column.name)
+            .collect()
+    }
+
+    /// Set columns of PostgreSQL table for each struct (generated by `#[derive(Table)]`)
+    ///
+    /// If it is not empty, it is the default field list of `SELECT` and `RETURNING` queries, and columns
+    /// without `skip_insert` are the default field list of `INSERT` queries
+    fn columns() -> &'static [SQLColumn] {
+        &[]
+    }
+
+    /// Set field values of a row, in the order of [`columns`] (or its PostgreSQL fields, used by [`copy_in`])
+    ///
+    /// It is generated by `#[derive(Table)]`, and it is required by functions that insert or update a row
+    /// (e.g. [`copy_in`] and [`insert_entity`]); without it, they return an error
+    ///
+    /// ```
+    /// fn row_values(row: &Self::RowType) -> Result<Vec<&(dyn ToSql + Sync)>, SQLError> {
+    ///     Ok(vec![&row.id, &row.name])
+    /// }
+    ///```
+    ///
+    /// [`columns`]: #method.columns
+    /// [`copy_in`]: #method.copy_in
+    /// [`insert_entity`]: #method.insert_entity
+    fn row_values(_row: &Self::RowType) -> Result<Vec<&(dyn ToSql + Sync)>, SQLError> {
+        Err(format!(
+            "row_values is not implemented for {}! (use #[derive(Table)])",
+            Self::table_name()
+        )
+        .into())
+    }
+
+    /// This function returns names of [`columns`] without `skip_insert`; `None` if no columns are set
+    ///
+    /// [`columns`]: #method.columns
+    fn insert_field_list() -> Option<Vec<&'static str>> {
+        match Self::columns() {
+            [] => None,
+            columns => Some(
+                columns
+                    .iter()
+                    .filter(|column| !column.skip_insert)
+                    .map(|column| column.name)
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Like [`row_values`], but only returns values of [`insert_field_list`] fields
+    ///
+    /// [`row_values`]: #method.row_values
+    /// [`insert_field_list`]: #method.insert_field_list
+    fn insert_values(row: &Self::RowType) -> Result<Vec<&(dyn ToSql + Sync)>, SQLError> {
+        match Self::columns() {
+            [] => Self::row_values(row),
+            columns => Ok(columns
+                .iter()
+                .zip(Self::row_values(row)?)
+                .filter(|(column, _)| !column.skip_insert)
+                .map(|(_, value)| value)
+                .collect()),
+        }
+    }
+
+    /// This function creates a fluent `SELECT` query builder of this table (see `Select`)
+    ///
+    /// ```no_run
+    /// let rows = ExampleTable::select_builder().filter(SQLCondition::EQUAL("name"), &[&name]).limit(10).fetch_all(&pool).await?;
+    /// ```
+    fn select_builder() -> Select<'a, Self>
+    where
+        Self: Sized + Send,
+    {
+        Select::new()
+    }
+
+    /// This function creates a fluent `GROUP BY` query builder of this table (see `GroupBy`)
+    ///
+    /// ```no_run
+    /// #[derive(FromRow)]
+    /// struct NameCount { name: String, total: i64 }
+    ///
+    /// let rows: Vec<NameCount> = ExampleTable::group_by_builder().group("name").aggregate(SQLAggregate::COUNT, "total").fetch_as(&pool).await?;
+    /// ```
+    fn group_by_builder() -> GroupBy<'a, Self>
+    where
+        Self: Sized + Send,
+    {
+        GroupBy::new()
+    }
+
+    /// This function creates a fluent `INSERT` query builder of this table (see `Insert`)
+    fn insert_builder() -> Insert<'a, Self>
+    where
+        Self: Sized + Send,
+    {
+        Insert::new()
+    }
+
+    /// This function creates a fluent `UPDATE` query builder of this table (see `Update`)
+    fn update_builder() -> Update<'a, Self>
+    where
+        Self: Sized + Send,
+    {
+        Update::new()
+    }
+
+    /// This function creates a fluent `DELETE` query builder of this table (see `Delete`)
+    fn delete_builder() -> Delete<'a, Self>
+    where
+        Self: Sized + Send,
+    {
+        Delete::new()
+    }
+
     /// Creates a new prepared statement.
     ///
     /// Prepared statements can be executed repeatedly, and may contain query parameters (indicated by `$1`, `$2`, etc),
@@ -56,7 +215,10 @@ pub trait DPQueryable<'a> {
     /// Like [`prepare`], but reads cached statements first
     ///
     /// [`prepare`]: #method.prepare
-    async fn prepare_cached(client: &Client, query: &str) -> Result<Statement, SQLError> {
+    async fn prepare_cached(
+        client: &deadpool_postgres::Client,
+        query: &str,
+    ) -> Result<Statement, SQLError> {
         Ok(client.prepare_cached(query).await?)
     }
 
@@ -66,16 +228,21 @@ pub trait DPQueryable<'a> {
     }
 
     /// Parse QueryType to load a query from raw string, file, or lib folder
-    async fn query_as_string(
-        query: &QueryType,
-        pool: Option<&PgPools>,
-    ) -> Result<String, SQLError> {
+    ///
+    /// `LIB` queries are read from the cached query lib of `executor` (see `SQLQueryLib`), without reading any file;
+    /// a missing query (or no `executor`) results `SQLError::QueryNotFoundError`, and a query lib that is not
+    /// loaded results its load error
+    async fn query_as_string<E>(query: &QueryType, executor: Option<&E>) -> Result<String, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
         Ok(match query {
             QueryType::RAW(query) => query.to_string(),
-            QueryType::FILE(file) => Self::read_sql_file(file)?,
-            QueryType::LIB(lib) => {
-                Self::read_sql_file(&format!("{}/{}", pool.unwrap().query_lib_path, lib))?
-            }
+            QueryType::FILE(file) => tokio::fs::read_to_string(file).await?,
+            QueryType::LIB(lib) => match executor {
+                None => return Err(SQLError::QueryNotFoundError(lib.to_owned())),
+                Some(executor) => executor.query_lib()?.query(lib)?.to_owned(),
+            },
         })
     }
 
@@ -89,15 +256,18 @@ pub trait DPQueryable<'a> {
     /// with the `prepare` method.
     ///
     /// If the statement does not modify any rows (e.g. `SELECT`), 0 is returned.
-    async fn execute(
-        pool: &PgPools,
+    async fn execute<E>(
+        executor: &E,
         query: QueryType,
         params: &[&(dyn ToSql + Sync)],
         is_read_only: bool,
-    ) -> Result<u64, SQLError> {
-        let client = pool.connection(is_read_only).get().await?;
-        let query_str = Self::query_as_string(&query, Some(&pool)).await?;
-        let statement = Self::prepare_cached(&client, &query_str).await?;
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let client = executor.acquire(is_read_only).await?;
+        let query_str = Self::query_as_string(&query, Some(executor)).await?;
+        let statement = client.statement(&query_str).await?;
         debug!("Execute {}", query_str);
         Ok(client.execute(&statement, params).await?)
     }
@@ -108,20 +278,21 @@ pub trait DPQueryable<'a> {
     /// provided, 1-indexed.
     ///
     /// [`execute`]: #method.execute
-    async fn execute_raw<P, I>(
-        pool: &PgPools,
+    async fn execute_raw<P, I, E>(
+        executor: &E,
         query: QueryType,
         params: I,
         is_read_only: bool,
     ) -> Result<u64, SQLError>
     where
+        E: PgExecutor + ?Sized,
         P: ToSql,
         I: IntoIterator<Item = P> + Sync + Send,
         I::IntoIter: ExactSizeIterator,
     {
-        let client = pool.connection(is_read_only).get().await?;
-        let query_str = Self::query_as_string(&query, Some(&pool)).await?;
-        let statement = Self::prepare_cached(&client, &query_str).await?;
+        let client = executor.acquire(is_read_only).await?;
+        let query_str = Self::query_as_string(&query, Some(executor)).await?;
+        let statement = client.statement(&query_str).await?;
         debug!("Execute raw {}", query_str);
         Ok(client.execute_raw(&statement, params).await?)
     }
@@ -130,36 +301,45 @@ pub trait DPQueryable<'a> {
     ///
     /// A statement may contain parameters, specified by `$n`, where `n` is the index of the parameter of the list
     /// provided, 1-indexed.
-    async fn query(
-        pool: &PgPools,
+    async fn query<E>(
+        executor: &E,
         query: QueryType,
         params: &[&(dyn ToSql + Sync)],
         is_read_only: bool,
-    ) -> Result<Vec<Row>, SQLError> {
-        let client = pool.connection(is_read_only).get().await?;
-        let query_str = Self::query_as_string(&query, Some(&pool)).await?;
-        let statement = Self::prepare_cached(&client, &query_str).await?;
+    ) -> Result<Vec<Row>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let client = executor.acquire(is_read_only).await?;
+        let query_str = Self::query_as_string(&query, Some(executor)).await?;
+        let statement = client.statement(&query_str).await?;
         debug!("Query {}", query_str);
         Ok(client.query(&statement, params).await?)
     }
 
     /// Executes a statement which returns a single row, returning it.
     ///
-    /// Returns an error if the query does not return exactly one row.
+    /// Returns an error if the query does not return exactly one row (`SQLError::NotFoundError` if no row).
     ///
     /// A statement may contain parameters, specified by `$n`, where `n` is the index of the parameter of the list
     /// provided, 1-indexed.
-    async fn query_one(
-        pool: &PgPools,
+    async fn query_one<E>(
+        executor: &E,
         query: QueryType,
         params: &[&(dyn ToSql + Sync)],
         is_read_only: bool,
-    ) -> Result<Row, SQLError> {
-        let client = pool.connection(is_read_only).get().await?;
-        let query_str = Self::query_as_string(&query, Some(&pool)).await?;
-        let statement = Self::prepare_cached(&client, &query_str).await?;
+    ) -> Result<Row, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let client = executor.acquire(is_read_only).await?;
+        let query_str = Self::query_as_string(&query, Some(executor)).await?;
+        let statement = client.statement(&query_str).await?;
         debug!("Query one {}", query_str);
-        Ok(client.query_one(&statement, params).await?)
+        client
+            .query_opt(&statement, params)
+            .await?
+            .ok_or(SQLError::NotFoundError)
     }
 
     /// Executes a statements which returns zero or one rows, returning it.
@@ -168,15 +348,18 @@ pub trait DPQueryable<'a> {
     ///
     /// A statement may contain parameters, specified by `$n`, where `n` is the index of the parameter of the list
     /// provided, 1-indexed.
-    async fn query_opt(
-        pool: &PgPools,
+    async fn query_opt<E>(
+        executor: &E,
         query: QueryType,
         params: &[&(dyn ToSql + Sync)],
         is_read_only: bool,
-    ) -> Result<Option<Row>, SQLError> {
-        let client = pool.connection(is_read_only).get().await?;
-        let query_str = Self::query_as_string(&query, Some(&pool)).await?;
-        let statement = Self::prepare_cached(&client, &query_str).await?;
+    ) -> Result<Option<Row>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let client = executor.acquire(is_read_only).await?;
+        let query_str = Self::query_as_string(&query, Some(executor)).await?;
+        let statement = client.statement(&query_str).await?;
         debug!("Query opt {}", query_str);
         Ok(client.query_opt(&statement, params).await?)
     }
@@ -187,29 +370,84 @@ pub trait DPQueryable<'a> {
     /// provided, 1-indexed.
     ///
     /// [`query`]: #method.query
-    async fn query_raw<I, P>(
-        pool: &PgPools,
+    async fn query_raw<I, P, E>(
+        executor: &E,
         query: QueryType,
         params: I,
         is_read_only: bool,
     ) -> Result<RowStream, SQLError>
     where
+        E: PgExecutor + ?Sized,
         P: ToSql,
         I: IntoIterator<Item = P> + Sync + Send,
         I::IntoIter: ExactSizeIterator,
     {
-        let client = pool.connection(is_read_only).get().await?;
-        let query_str = Self::query_as_string(&query, Some(&pool)).await?;
-        let statement = Self::prepare_cached(&client, &query_str).await?;
+        let client = executor.acquire(is_read_only).await?;
+        let query_str = Self::query_as_string(&query, Some(executor)).await?;
+        let statement = client.statement(&query_str).await?;
         debug!("Query raw {}", query_str);
         Ok(client.query_raw(&statement, params).await?)
     }
 
+    /// Executes a sequence of SQL statements using the simple query protocol, returning the resulting rows.
+    ///
+    /// Statements should be separated by semicolons. If an error occurs, execution of the sequence will stop at that point. The simple query protocol returns the values in rows as strings rather than in their binary encodings, so the associated row type doesn’t work with the FromSql trait. Rather than simply returning a list of the rows, this method returns a list of an enum which indicates either the completion of one of the commands, or a row of data. This preserves the framing between the separate statements in the request.
+    async fn simple_query<E>(
+        executor: &E,
+        query: QueryType,
+        is_read_only: bool,
+    ) -> Result<Vec<SimpleQueryMessage>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let client = executor.acquire(is_read_only).await?;
+        let query = Self::query_as_string(&query, Some(executor)).await?;
+        debug!("Query {}", query);
+        Ok(client.simple_query(&query).await?)
+    }
+
     /// This function converts PostgreSQL Row type to provided type in RowType section (Rust struct type)
     fn parse_type(row: &Row) -> Result<Self::RowType, SQLError> {
         Self::parse_generic_type::<Self::RowType>(row)
     }
 
+    /// Like [`parse_type`], but reads the fields of RowType from columns `offset..` of the row, in the order of
+    /// [`columns`] (e.g. the second table of a `JOIN`, whose column names are the same as the first one)
+    ///
+    /// `#[derive(Table)]` generates it; by default, it reads columns by their names (see [`parse_type`]), which is
+    /// only correct for `offset = 0` (the first match of a name), so other offsets return an error
+    ///
+    /// [`parse_type`]: #method.parse_type
+    /// [`columns`]: #method.columns
+    fn parse_type_at(row: &Row, offset: usize) -> Result<Self::RowType, SQLError> {
+        match offset {
+            0 => Self::parse_type(row),
+            _ => Err(format!(
+                "parse_type_at is not implemented for {}! (use #[derive(Table)])",
+                Self::table_name()
+            )
+            .into()),
+        }
+    }
+
+    /// Like [`parse_type_at`], but returns `None` if all [`columns`] from `offset` are `NULL` (e.g. `LEFT JOIN`)
+    ///
+    /// [`parse_type_at`]: #method.parse_type_at
+    /// [`columns`]: #method.columns
+    fn parse_type_opt_at(row: &Row, offset: usize) -> Result<Option<Self::RowType>, SQLError> {
+        let count = Self::columns().len();
+        let is_null = count > 0
+            && (offset..offset + count)
+                .map(|index| row.try_get::<_, SQLIsNull>(index).map(|value| value.0))
+                .collect::<Result<Vec<bool>, _>>()?
+                .into_iter()
+                .all(|value| value);
+        match is_null {
+            true => Ok(None),
+            false => Ok(Some(Self::parse_type_at(row, offset)?)),
+        }
+    }
+
     /// This function converts PostgreSQL Row type to any generic Rust type with FromRow trait implementation
     fn parse_generic_type<T>(row: &Row) -> Result<T, SQLError>
     where
@@ -221,13 +459,16 @@ pub trait DPQueryable<'a> {
     /// Like [`query`], but parse result to a vector of RowType
     ///
     /// [`query`]: #method.query
-    async fn query_typed(
-        pool: &PgPools,
+    async fn query_typed<E>(
+        executor: &E,
         query: QueryType,
         params: &[&(dyn ToSql + Sync)],
         is_read_only: bool,
-    ) -> Result<Vec<Self::RowType>, SQLError> {
-        let raws = Self::query(pool, query, params, is_read_only).await?;
+    ) -> Result<Vec<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let raws = Self::query(executor, query, params, is_read_only).await?;
         raws.into_iter()
             .map(|row| {
                 let res = Self::parse_type(&row)?;
@@ -239,26 +480,32 @@ pub trait DPQueryable<'a> {
     /// Like [`query_one`], but parse result to a RowType
     ///
     /// [`query_one`]: #method.query_one
-    async fn query_one_typed(
-        pool: &PgPools,
+    async fn query_one_typed<E>(
+        executor: &E,
         query: QueryType,
         params: &[&(dyn ToSql + Sync)],
         is_read_only: bool,
-    ) -> Result<Self::RowType, SQLError> {
-        let row = Self::query_one(pool, query, params, is_read_only).await?;
+    ) -> Result<Self::RowType, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let row = Self::query_one(executor, query, params, is_read_only).await?;
         Ok(Self::parse_type(&row)?)
     }
 
     /// Like [`query_opt`], but parse result to a optional RowType
     ///
     /// [`query_opt`]: #method.query_opt
-    async fn query_opt_typed(
-        pool: &PgPools,
+    async fn query_opt_typed<E>(
+        executor: &E,
         query: QueryType,
         params: &[&(dyn ToSql + Sync)],
         is_read_only: bool,
-    ) -> Result<Option<Self::RowType>, SQLError> {
-        match Self::query_opt(pool, query, params, is_read_only).await? {
+    ) -> Result<Option<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        match Self::query_opt(executor, query, params, is_read_only).await? {
             None => Ok(None),
             Some(row) => Ok(Some(Self::parse_type(&row)?)),
         }
@@ -267,164 +514,600 @@ pub trait DPQueryable<'a> {
     /// Like [`query_raw`], but parse result to a vector of RowType
     ///
     /// [`query_raw`]: #method.query_raw
-    async fn query_raw_typed<I, P>(
-        pool: &PgPools,
+    async fn query_raw_typed<I, P, E>(
+        executor: &E,
         query: QueryType,
         params: I,
         is_read_only: bool,
     ) -> Result<Vec<Self::RowType>, SQLError>
     where
+        E: PgExecutor + ?Sized,
         P: ToSql,
         I: IntoIterator<Item = P> + Sync + Send,
         I::IntoIter: ExactSizeIterator,
     {
-        let mut result: Vec<Self::RowType> = Vec::new();
-        let raws = Self::query_raw(pool, query, params, is_read_only).await?;
-        pin_mut!(raws);
-        while let Some(row) = raws.try_next().await? {
-            let res = Self::parse_type(&row)?;
-            result.push(res);
-        }
-        Ok(result)
+        Self::query_stream_typed(executor, query, params, is_read_only)
+            .await?
+            .try_collect()
+            .await
     }
 
-    /// This function convert an optional vector of string to a list of PostgreSQL fields;
-    /// `None` results `*`
-    fn field_query_builder(field_list: Option<Vec<&str>>) -> String {
-        match field_list {
-            None => "*".to_owned(),
-            Some(items) => match items.len() {
-                0 => "*".to_owned(),
-                _ => items.join(", "),
+    /// Like [`query_raw`], but returns a stream of RowType; the stream holds its connection until it is dropped,
+    /// so rows are parsed one by one instead of collecting all of them in memory; it ends after the first error
+    ///
+    /// ```no_run
+    /// let rows = ExampleTable::query_stream_typed(&pool, QueryType::RAW(query), params, true).await?;
+    /// pin_mut!(rows);
+    /// while let Some(row) = rows.try_next().await? {
+    ///     println!("{:?}", row);
+    /// }
+    /// ```
+    ///
+    /// [`query_raw`]: #method.query_raw
+    async fn query_stream_typed<'e, I, P, E>(
+        executor: &'e E,
+        query: QueryType,
+        params: I,
+        is_read_only: bool,
+    ) -> Result<BoxStream<'e, Result<Self::RowType, SQLError>>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        P: BorrowToSql,
+        I: IntoIterator<Item = P> + Sync + Send,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let client = executor.acquire(is_read_only).await?;
+        let query_str = Self::query_as_string(&query, Some(executor)).await?;
+        let statement = client.statement(&query_str).await?;
+        debug!("Query stream {}", query_str);
+        let rows = Box::pin(client.query_raw(&statement, params).await?);
+        Ok(stream::unfold(Some((client, rows)), |state| async move {
+            let (client, mut rows) = state?;
+            match rows.try_next().await {
+                Ok(None) => None,
+                Ok(Some(row)) => match Self::parse_type(&row) {
+                    Ok(row) => Some((Ok(row), Some((client, rows)))),
+                    Err(e) => Some((Err(e), None)),
+                },
+                Err(e) => Some((Err(e.into()), None)),
+            }
+        })
+        .boxed())
+    }
+
+    /// This function returns the quoted PostgreSQL table name (see `SQLIdentifier`); `None` results [`table_name`]
+    ///
+    /// [`table_name`]: #method.table_name
+    fn table_query_builder(table_name: Option<&str>) -> Result<String, SQLError> {
+        let table_name = match table_name {
+            None => Self::table_name(),
+            Some(name) => name,
+        };
+        SQLIdentifier::quote(table_name)
+    }
+
+    /// This function convert an optional vector of string to a list of quoted PostgreSQL fields;
+    /// `None` results [`columns`] (or `*` if no columns are set)
+    ///
+    /// [`columns`]: #method.columns
+    fn field_query_builder(field_list: Option<Vec<&str>>) -> Result<String, SQLError> {
+        let fields = match field_list {
+            Some(items) if !items.is_empty() => items
+                .into_iter()
+                .map(SQLIdentifier::quote_field)
+                .collect::<Result<Vec<String>, SQLError>>()?,
+            _ => match Self::columns() {
+                [] => vec!["*".to_owned()],
+                columns => columns
+                    .iter()
+                    .map(SQLColumn::select_field)
+                    .collect::<Result<Vec<String>, SQLError>>()?,
             },
-        }
+        };
+        Ok(fields.join(", "))
+    }
+
+    /// This function converts a list of fields to quoted PostgreSQL identifiers (e.g. `INSERT` or `UPDATE` fields)
+    fn identifier_query_builder(field_list: &[&str]) -> Result<Vec<String>, SQLError> {
+        field_list
+            .iter()
+            .map(|field| SQLIdentifier::quote(field))
+            .collect()
     }
 
     /// This function converts a vector of Rust `SQLCondition` values to PostgreSQL `WHERE` params
-    fn filter_query_builder(filter_list: Option<Vec<SQLCondition<'_>>>, offset: i32) -> String {
+    fn filter_query_builder(
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        offset: i32,
+    ) -> Result<String, SQLError> {
+        Self::condition_query_builder("WHERE", filter_list, offset, &SQLIdentifier::quote)
+    }
+
+    /// Like [`filter_query_builder`], but for any clause `keyword` (e.g. `HAVING`); fields are rendered by `quote`
+    ///
+    /// [`filter_query_builder`]: #method.filter_query_builder
+    fn condition_query_builder(
+        keyword: &str,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        offset: i32,
+        quote: &dyn Fn(&str) -> Result<String, SQLError>,
+    ) -> Result<String, SQLError> {
         match filter_list {
-            None => return "".to_owned(),
+            None => Ok("".to_owned()),
             Some(filters) => match filters.len() {
-                0 => return "".to_owned(),
+                0 => Ok("".to_owned()),
                 _ => {
                     let mut filter_index = offset;
-                    let filter_query: Vec<String> = filters
+                    let filter_query = filters
                         .into_iter()
-                        .map(|filter| match filter {
-                            SQLCondition::OR | SQLCondition::AND => filter.to_string(),
-                            _ => {
+                        .map(|filter| {
+                            let mut query = filter.render(quote)?;
+                            for _ in 0..filter.param_count() {
                                 filter_index += 1;
                                 let s = format!("${}", filter_index);
-                                filter.to_string().replace("##ID##", &s)
+                                query = query.replacen("##ID##", &s, 1);
                             }
+                            Ok(query)
                         })
-                        .collect();
-                    return format!(" WHERE {} ", filter_query.join(""));
+                        .collect::<Result<Vec<String>, SQLError>>()?;
+                    Ok(format!(" {} {} ", keyword, filter_query.join("")))
                 }
             },
         }
     }
 
-    /// This function converts a list of optional string to PostgreSQL sorting params (`ORDER BY ...`)
-    fn sort_query_builder(sort_list: Option<Vec<&str>>, sort_type: Option<SQLSort>) -> String {
+    /// This function converts a list of optional `SQLOrder` to PostgreSQL sorting params (`ORDER BY ...`);
+    /// fields without their own direction are sorted by `sort_type` (`ASC` if `None`)
+    fn sort_query_builder(
+        sort_list: Option<Vec<SQLOrder<'_>>>,
+        sort_type: Option<SQLSort>,
+    ) -> Result<String, SQLError> {
         match sort_list {
-            None => "".to_owned(),
+            None => Ok("".to_owned()),
             Some(items) => match items.len() {
-                0 => return "".to_owned(),
+                0 => Ok("".to_owned()),
                 _ => {
-                    let sort_order = match sort_type {
-                        None => "ASC".to_owned(),
-                        Some(sort) => sort.to_string(),
-                    };
-                    return format!(" ORDER BY {} {} ", items.join(", "), sort_order);
+                    let sort_type = sort_type.unwrap_or(SQLSort::ASC);
+                    let sort_order = items
+                        .into_iter()
+                        .map(|item| {
+                            SQLOrder {
+                                sort: item.sort.or(Some(sort_type)),
+                                ..item
+                            }
+                            .to_query()
+                        })
+                        .collect::<Result<Vec<String>, SQLError>>()?;
+                    Ok(format!(" ORDER BY {} ", sort_order.join(", ")))
                 }
             },
         }
     }
 
+    /// This function generates the keyset condition of `sort_list` against the cursor values `$offset+1 ...`;
+    /// `(a, b) > ($1, $2)` if all fields have the same direction, otherwise `a > $1 OR (a = $1 AND b < $2)`
+    fn keyset_query_builder(
+        sort_list: &[SQLOrder<'_>],
+        sort_type: Option<SQLSort>,
+        offset: usize,
+    ) -> Result<String, SQLError> {
+        let sort_type = sort_type.unwrap_or(SQLSort::ASC);
+        let fields = sort_list
+            .iter()
+            .map(|item| SQLIdentifier::quote(item.field))
+            .collect::<Result<Vec<String>, SQLError>>()?;
+        let operators: Vec<&str> = sort_list
+            .iter()
+            .map(|item| match item.sort.unwrap_or(sort_type) {
+                SQLSort::ASC => ">",
+                SQLSort::DESC => "<",
+            })
+            .collect();
+        let params: Vec<String> = (offset + 1..offset + sort_list.len() + 1)
+            .map(|index| format!("${}", index))
+            .collect();
+        if operators.iter().all(|operator| *operator == operators[0]) {
+            return Ok(format!(
+                "({}) {} ({})",
+                fields.join(", "),
+                operators[0],
+                params.join(", ")
+            ));
+        }
+        let branches: Vec<String> = (0..sort_list.len())
+            .map(|index| {
+                let mut terms: Vec<String> = (0..index)
+                    .map(|prev| format!("{} = {}", fields[prev], params[prev]))
+                    .collect();
+                terms.push(format!(
+                    "{} {} {}",
+                    fields[index], operators[index], params[index]
+                ));
+                format!("({})", terms.join(" AND "))
+            })
+            .collect();
+        Ok(format!("({})", branches.join(" OR ")))
+    }
+
+    /// This function converts an optional `SQLLimit` to PostgreSQL `LIMIT ... OFFSET ...` params
+    fn limit_query_builder(limit: Option<SQLLimit>) -> String {
+        match limit {
+            None => "".to_owned(),
+            Some(limit) => limit.to_string(),
+        }
+    }
+
+    /// Like [`field_query_builder`], but [`columns`] are qualified with a table alias (`"alias"."name" AS "field"`);
+    /// `alias.*` if no columns are set
+    ///
+    /// [`field_query_builder`]: #method.field_query_builder
+    /// [`columns`]: #method.columns
+    fn alias_field_query_builder(alias: &str) -> Result<String, SQLError> {
+        match Self::columns() {
+            [] => Ok(format!("{}.*", SQLIdentifier::quote(alias)?)),
+            columns => Ok(columns
+                .iter()
+                .map(|column| column.select_field_of(alias))
+                .collect::<Result<Vec<String>, SQLError>>()?
+                .join(", ")),
+        }
+    }
+
+    /// This function generates `FROM` list of a query: the table (with an optional alias) and its joins
+    fn from_query_builder(
+        table_name: Option<&str>,
+        alias: Option<&str>,
+        join_list: &[SQLJoin<'_>],
+    ) -> Result<String, SQLError> {
+        let mut query = Self::table_query_builder(table_name)?;
+        if let Some(alias) = alias {
+            query = format!("{} AS {}", query, SQLIdentifier::quote(alias)?);
+        }
+        for join in join_list {
+            query.push_str(&join.to_query()?);
+        }
+        Ok(query)
+    }
+
     /// This function generates `SELECT` query
     fn select_query_builder(
         table_name: Option<&str>,
         field_list: Option<Vec<&str>>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
-        sort_list: Option<Vec<&str>>,
+        sort_list: Option<Vec<SQLOrder<'_>>>,
         sort_type: Option<SQLSort>,
-    ) -> String {
-        let table_name = match table_name {
-            None => Self::table_name(),
-            Some(name) => name,
-        };
-        let fields = Self::field_query_builder(field_list);
-        let filters = Self::filter_query_builder(filter_list, 0);
-        let sorts = Self::sort_query_builder(sort_list, sort_type);
-        format!(
-            "SELECT {} FROM {} {} {}",
-            fields, table_name, filters, sorts
+        limit: Option<SQLLimit>,
+    ) -> Result<String, SQLError> {
+        let fields = Self::field_query_builder(field_list)?;
+        Self::select_join_query_builder(
+            table_name,
+            None,
+            &[],
+            fields,
+            filter_list,
+            sort_list,
+            sort_type,
+            limit,
         )
     }
 
+    /// Like [`select_query_builder`], but selects from a table `alias` with a list of joins;
+    /// `fields` are already quoted (e.g. by [`alias_field_query_builder`])
+    ///
+    /// [`select_query_builder`]: #method.select_query_builder
+    /// [`alias_field_query_builder`]: #method.alias_field_query_builder
+    #[allow(clippy::too_many_arguments)]
+    fn select_join_query_builder(
+        table_name: Option<&str>,
+        alias: Option<&str>,
+        join_list: &[SQLJoin<'_>],
+        fields: String,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        sort_list: Option<Vec<SQLOrder<'_>>>,
+        sort_type: Option<SQLSort>,
+        limit: Option<SQLLimit>,
+    ) -> Result<String, SQLError> {
+        let table_name = Self::from_query_builder(table_name, alias, join_list)?;
+        let filters = Self::filter_query_builder(filter_list, 0)?;
+        let sorts = Self::sort_query_builder(sort_list, sort_type)?;
+        let limits = Self::limit_query_builder(limit);
+        Ok(format!(
+            "SELECT {} FROM {} {} {} {}",
+            fields, table_name, filters, sorts, limits
+        ))
+    }
+
     /// Running a `SELECT` query and return a vector of PostgreSQL `Row` type
-    async fn select(
-        pool: &PgPools,
+    #[allow(clippy::too_many_arguments)]
+    async fn select<E>(
+        executor: &E,
         table_name: Option<&str>,
         field_list: Option<Vec<&str>>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-        sort_list: Option<Vec<&str>>,
+        sort_list: Option<Vec<SQLOrder<'_>>>,
         sort_type: Option<SQLSort>,
-    ) -> Result<Vec<Row>, SQLError> {
-        let query =
-            Self::select_query_builder(table_name, field_list, filter_list, sort_list, sort_type);
-        Self::query(pool, QueryType::RAW(query), filter_values, true).await
+        limit: Option<SQLLimit>,
+    ) -> Result<Vec<Row>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = Self::select_query_builder(
+            table_name,
+            field_list,
+            filter_list,
+            sort_list,
+            sort_type,
+            limit,
+        )?;
+        Self::query(executor, QueryType::RAW(query), filter_values, true).await
+    }
+
+    /// This function replaces `$n` params of a query with their values as quoted PostgreSQL literals;
+    /// values are quoted by PostgreSQL itself (`quote_nullable`) and casted to the type of their params
+    async fn literal_query_builder(
+        client: &Client,
+        query: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<String, SQLError> {
+        if params.is_empty() {
+            return Ok(query.to_owned());
+        }
+        let types: Vec<Type> = client.prepare(query).await?.params().to_vec();
+        if types.len() != params.len() {
+            return Err(format!(
+                "Query has {} params, but {} values are provided!",
+                types.len(),
+                params.len()
+            )
+            .into());
+        }
+        let quotes = types
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| {
+                format!(
+                    "quote_nullable(${}) || '::' || format_type({}, NULL)",
+                    i + 1,
+                    ty.oid()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        let statement = client
+            .prepare_typed(&format!("SELECT {}", quotes), &types)
+            .await?;
+        let row = client.query_one(&statement, params).await?;
+        let literals = (0..params.len())
+            .map(|i| row.try_get::<_, String>(i))
+            .collect::<Result<Vec<String>, _>>()?;
+        Self::param_literal_query_builder(query, &literals)
+    }
+
+    /// This function replaces `$n` params of a query with `literals` (`$1` is the first one); string literals,
+    /// quoted identifiers, comments and dollar-quoted strings are kept as they are
+    fn param_literal_query_builder(query: &str, literals: &[String]) -> Result<String, SQLError> {
+        let chars: Vec<char> = query.chars().collect();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+        let mut result = String::with_capacity(query.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let start = i;
+            match chars[i] {
+                quote @ ('\'' | '"') => {
+                    let escape = quote == '\''
+                        && i > 0
+                        && chars[i - 1].eq_ignore_ascii_case(&'e')
+                        && (i < 2 || !is_word(chars[i - 2]));
+                    i += 1;
+                    while i < chars.len() && chars[i] != quote {
+                        i += if escape && chars[i] == '\\' { 2 } else { 1 };
+                    }
+                    i += 1;
+                }
+                '-' if chars.get(i + 1) == Some(&'-') => {
+                    while i < chars.len() && chars[i] != '\n' {
+                        i += 1;
+                    }
+                }
+                '/' if chars.get(i + 1) == Some(&'*') => {
+                    let mut depth = 0;
+                    while i < chars.len() {
+                        match (chars[i], chars.get(i + 1)) {
+                            ('/', Some('*')) => depth += 1,
+                            ('*', Some('/')) => depth -= 1,
+                            _ => {
+                                i += 1;
+                                continue;
+                            }
+                        }
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                '$' if i > 0 && is_word(chars[i - 1]) => i += 1,
+                '$' if chars.get(i + 1).is_some_and(char::is_ascii_digit) => {
+                    let mut index = 0usize;
+                    i += 1;
+                    while let Some(digit) = chars.get(i).and_then(|c| c.to_digit(10)) {
+                        index = index * 10 + digit as usize;
+                        i += 1;
+                    }
+                    match literals.get(index.wrapping_sub(1)) {
+                        None => return Err(format!("Query param ${} has no value!", index).into()),
+                        Some(literal) => result.push_str(&format!("({})", literal)),
+                    }
+                    continue;
+                }
+                '$' => {
+                    let mut end = i + 1;
+                    while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                        end += 1;
+                    }
+                    i += 1;
+                    if chars.get(end) == Some(&'$') {
+                        let tag = &chars[start..=end];
+                        i = end + 1;
+                        while i < chars.len() && !chars[i..].starts_with(tag) {
+                            i += 1;
+                        }
+                        i = (i + tag.len()).min(chars.len());
+                    }
+                }
+                _ => i += 1,
+            }
+            let end = i.min(chars.len());
+            result.extend(&chars[start..end]);
+            i = end;
+        }
+        Ok(result)
+    }
+
+    /// Like [`select`], but writes the rows to `writer` as CSV (with header) using `COPY (SELECT ...) TO STDOUT`,
+    /// or as JSON Lines of serialized `RowType` (or of `row_to_json` objects of `field_list` fields, if it is set)
+    ///
+    /// `COPY` does not accept params, so `filter_values` are inlined by [`literal_query_builder`]
+    ///
+    /// ```no_run
+    /// let mut file = tokio::fs::File::create("example.csv").await?;
+    /// ExampleTable::export(&pool, None, None, Some(vec![SQLCondition::GE("id")]), &[&100i64], None, None, None, SQLFormat::CSV, &mut file).await?;
+    /// ```
+    ///
+    /// [`select`]: #method.select
+    /// [`literal_query_builder`]: #method.literal_query_builder
+    #[allow(clippy::too_many_arguments)]
+    async fn export<E, W>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_list: Option<Vec<&str>>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+        sort_list: Option<Vec<SQLOrder<'_>>>,
+        sort_type: Option<SQLSort>,
+        limit: Option<SQLLimit>,
+        format: SQLFormat,
+        writer: &mut W,
+    ) -> Result<(), SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        W: AsyncWrite + Unpin + Send + ?Sized,
+    {
+        let is_row_type = field_list.is_none();
+        let query = Self::select_query_builder(
+            table_name,
+            field_list,
+            filter_list,
+            sort_list,
+            sort_type,
+            limit,
+        )?;
+        let client = executor.acquire(true).await?;
+        match format {
+            SQLFormat::CSV => {
+                let query = Self::literal_query_builder(&client, &query, filter_values).await?;
+                let query = format!("COPY ({}) TO STDOUT (FORMAT csv, HEADER)", query);
+                debug!("Copy out {}", query);
+                let stream = client.copy_out(&query).await?;
+                pin_mut!(stream);
+                while let Some(chunk) = stream.try_next().await? {
+                    writer.write_all(&chunk).await?;
+                }
+            }
+            SQLFormat::JSON if !is_row_type => {
+                let query = format!("SELECT row_to_json(t)::text FROM ({}) AS t", query);
+                let statement = client.statement(&query).await?;
+                debug!("Query raw {}", query);
+                let rows = client
+                    .query_raw(&statement, filter_values.iter().copied())
+                    .await?;
+                pin_mut!(rows);
+                while let Some(row) = rows.try_next().await? {
+                    let line: String = row.try_get(0)?;
+                    writer.write_all(line.as_bytes()).await?;
+                    writer.write_all(b"\n").await?;
+                }
+            }
+            SQLFormat::JSON => {
+                let statement = client.statement(&query).await?;
+                debug!("Query raw {}", query);
+                let rows = client
+                    .query_raw(&statement, filter_values.iter().copied())
+                    .await?;
+                pin_mut!(rows);
+                while let Some(row) = rows.try_next().await? {
+                    let mut line =
+                        serde_json::to_vec(&Self::parse_type(&row)?).map_err(|e| e.to_string())?;
+                    line.push(b'\n');
+                    writer.write_all(&line).await?;
+                }
+            }
+        }
+        Ok(writer.flush().await?)
     }
 
     /// Like [`select`], but output should be just one row, unless cause error
     ///
     /// [`select`]: #method.select
-    async fn select_one(
-        pool: &PgPools,
+    async fn select_one<E>(
+        executor: &E,
         table_name: Option<&str>,
         field_list: Option<Vec<&str>>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<Row, SQLError> {
-        let query = Self::select_query_builder(table_name, field_list, filter_list, None, None);
-        Self::query_one(pool, QueryType::RAW(query), filter_values, true).await
+    ) -> Result<Row, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query =
+            Self::select_query_builder(table_name, field_list, filter_list, None, None, None)?;
+        Self::query_one(executor, QueryType::RAW(query), filter_values, true).await
     }
 
     /// Like [`select`], but output should be maximum one row or nothing, unless cause error
     ///
     /// [`select`]: #method.select
-    async fn select_opt(
-        pool: &PgPools,
+    async fn select_opt<E>(
+        executor: &E,
         table_name: Option<&str>,
         field_list: Option<Vec<&str>>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<Option<Row>, SQLError> {
-        let query = Self::select_query_builder(table_name, field_list, filter_list, None, None);
-        Self::query_opt(pool, QueryType::RAW(query), filter_values, true).await
+    ) -> Result<Option<Row>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query =
+            Self::select_query_builder(table_name, field_list, filter_list, None, None, None)?;
+        Self::query_opt(executor, QueryType::RAW(query), filter_values, true).await
     }
 
     /// Like [`select`], but parse output to Rust `RowType` provided in implementation of this trait
     ///
     /// [`select`]: #method.select
-    async fn select_typed(
-        pool: &PgPools,
+    #[allow(clippy::too_many_arguments)]
+    async fn select_typed<E>(
+        executor: &E,
         table_name: Option<&str>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-        sort_list: Option<Vec<&str>>,
+        sort_list: Option<Vec<SQLOrder<'_>>>,
         sort_type: Option<SQLSort>,
-    ) -> Result<Vec<Self::RowType>, SQLError> {
+        limit: Option<SQLLimit>,
+    ) -> Result<Vec<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
         let raws = Self::select(
-            pool,
+            executor,
             table_name,
             None,
             filter_list,
             filter_values,
             sort_list,
             sort_type,
+            limit,
         )
         .await?;
         raws.into_iter()
@@ -435,212 +1118,1609 @@ pub trait DPQueryable<'a> {
             .collect()
     }
 
-    /// Like [`select_one`], but parse output to `RowType`
+    /// Like [`select_typed`], but returns a stream of RowType (see [`query_stream_typed`])
     ///
-    /// [`select_one`]: #method.select_one
-    async fn select_one_typed(
-        pool: &PgPools,
+    /// [`select_typed`]: #method.select_typed
+    /// [`query_stream_typed`]: #method.query_stream_typed
+    #[allow(clippy::too_many_arguments)]
+    async fn select_stream_typed<'e, E>(
+        executor: &'e E,
         table_name: Option<&str>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<Self::RowType, SQLError> {
-        let row = Self::select_one(pool, table_name, None, filter_list, filter_values).await?;
-        Ok(Self::parse_type(&row)?)
+        sort_list: Option<Vec<SQLOrder<'_>>>,
+        sort_type: Option<SQLSort>,
+        limit: Option<SQLLimit>,
+    ) -> Result<BoxStream<'e, Result<Self::RowType, SQLError>>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query =
+            Self::select_query_builder(table_name, None, filter_list, sort_list, sort_type, limit)?;
+        Self::query_stream_typed(
+            executor,
+            QueryType::RAW(query),
+            filter_values.iter().copied(),
+            true,
+        )
+        .await
     }
 
-    /// Like [`select_opt`], but parse output to `RowType`
+    /// Like [`select_typed`], but returns one page of rows plus the total number of rows (using [`count`])
     ///
-    /// [`select_opt`]: #method.select_opt
-    async fn select_opt_typed(
-        pool: &PgPools,
+    /// ```no_run
+    /// let page = ExampleTable::select_page_typed(&pool, None, None, &[], Some(vec!["id".into()]), None, SQLLimit::page(2, 50)).await?;
+    /// println!("{} of {}", page.items.len(), page.total);
+    /// ```
+    ///
+    /// [`select_typed`]: #method.select_typed
+    /// [`count`]: #method.count
+    async fn select_page_typed<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+        sort_list: Option<Vec<SQLOrder<'_>>>,
+        sort_type: Option<SQLSort>,
+        limit: SQLLimit,
+    ) -> Result<SQLPage<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let total = Self::count(executor, table_name, filter_list.clone(), filter_values).await?;
+        let items = Self::select_typed(
+            executor,
+            table_name,
+            filter_list,
+            filter_values,
+            sort_list,
+            sort_type,
+            Some(limit),
+        )
+        .await?;
+        Ok(SQLPage {
+            items,
+            total,
+            limit: limit.limit,
+            offset: limit.offset,
+        })
+    }
+
+    /// Like [`select_typed`], but returns a keyset page of `page_size` rows after `cursor` (`None` for the first page)
+    ///
+    /// Rows are ordered by `sort_list` (fields without their own direction use `sort_type`) and filtered by
+    /// `(sort_list...) > (cursor values...)` (or `<` for `SQLSort::DESC`, see [`keyset_query_builder`]), so the sort columns should be `NOT NULL` and unique together (e.g. end with `id`)
+    ///
+    /// ```no_run
+    /// let sorts = vec![SQLOrder::desc("created_at"), SQLOrder::desc("id")];
+    /// let page = ExampleTable::select_keyset_typed(&pool, None, None, &[], sorts.clone(), None, None, 50).await?;
+    /// let next = ExampleTable::select_keyset_typed(&pool, None, None, &[], sorts, None, page.next_cursor.as_ref(), 50).await?;
+    /// ```
+    ///
+    /// [`select_typed`]: #method.select_typed
+    /// [`keyset_query_builder`]: #method.keyset_query_builder
+    #[allow(clippy::too_many_arguments)]
+    async fn select_keyset_typed<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+        sort_list: Vec<SQLOrder<'_>>,
+        sort_type: Option<SQLSort>,
+        cursor: Option<&SQLCursor>,
+        page_size: i64,
+    ) -> Result<SQLKeysetPage<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        if sort_list.is_empty() {
+            return Err("No sort field find!".to_owned().into());
+        }
+        let table_name = Self::table_query_builder(table_name)?;
+        let cursor_fields = sort_list
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let field = SQLIdentifier::quote(item.field)?;
+                Ok(format!("({})::text AS __cursor_{}", field, index))
+            })
+            .collect::<Result<Vec<String>, SQLError>>()?;
+        let offset: usize = filter_list
+            .iter()
+            .flatten()
+            .map(|filter| filter.param_count())
+            .sum();
+        let mut filters = Self::filter_query_builder(
+            filter_list
+                .filter(|filters| !filters.is_empty())
+                .map(|filters| vec![SQLCondition::GROUP(filters)]),
+            0,
+        )?;
+        let cursor_values: Vec<SQLTextValue> = match cursor {
+            None => Vec::new(),
+            Some(cursor) => cursor.decode()?.into_iter().map(SQLTextValue).collect(),
+        };
+        if cursor.is_some() {
+            if cursor_values.len() != sort_list.len() {
+                return Err("Cursor does not match sort fields!".to_owned().into());
+            }
+            let keyset = Self::keyset_query_builder(&sort_list, sort_type, offset)?;
+            filters = if filters.is_empty() {
+                format!(" WHERE {} ", keyset)
+            } else {
+                format!("{} AND {} ", filters, keyset)
+            };
+        }
+        let sorts = Self::sort_query_builder(Some(sort_list), sort_type)?;
+        let query = format!(
+            "SELECT {}, {} FROM {} {} {} LIMIT {}",
+            Self::field_query_builder(None)?,
+            cursor_fields.join(", "),
+            table_name,
+            filters,
+            sorts,
+            page_size + 1
+        );
+        let mut params: Vec<&(dyn ToSql + Sync)> = filter_values.to_vec();
+        params.extend(
+            cursor_values
+                .iter()
+                .map(|value| value as &(dyn ToSql + Sync)),
+        );
+        let mut rows = Self::query(executor, QueryType::RAW(query), &params, true).await?;
+        let mut next_cursor = None;
+        if rows.len() as i64 > page_size {
+            rows.truncate(page_size.max(0) as usize);
+            if let Some(last) = rows.last() {
+                let values = (0..cursor_fields.len())
+                    .map(|index| last.try_get(format!("__cursor_{}", index).as_str()))
+                    .collect::<Result<Vec<Option<String>>, _>>()?;
+                next_cursor = Some(SQLCursor::encode(&values));
+            }
+        }
+        let items = rows
+            .iter()
+            .map(|row| Self::parse_type(row))
+            .collect::<Result<Vec<Self::RowType>, SQLError>>()?;
+        Ok(SQLKeysetPage { items, next_cursor })
+    }
+
+    /// Like [`select_typed`], but declares a server-side cursor (`DECLARE ... CURSOR`) on `transaction` and
+    /// returns a stream of `batch_size` rows (`FETCH n`) per item, so memory stays bounded for huge tables
+    ///
+    /// The cursor is closed after the last batch; if the stream is dropped earlier, it is closed at the end of the transaction
+    ///
+    /// ```no_run
+    /// pool.transaction(|tx| Box::pin(async move {
+    ///     let batches = ExampleTable::select_cursor_typed(tx, None, None, &[], None, None, 1000).await?;
+    ///     pin_mut!(batches);
+    ///     while let Some(rows) = batches.try_next().await? {
+    ///         println!("{}", rows.len());
+    ///     }
+    ///     Ok(())
+    /// })).await?;
+    /// ```
+    ///
+    /// [`select_typed`]: #method.select_typed
+    #[allow(clippy::too_many_arguments)]
+    async fn select_cursor_typed<'t>(
+        transaction: &'t PgTransaction<'_>,
+        table_name: Option<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+        sort_list: Option<Vec<SQLOrder<'_>>>,
+        sort_type: Option<SQLSort>,
+        batch_size: i64,
+    ) -> Result<BoxStream<'t, Result<Vec<Self::RowType>, SQLError>>, SQLError> {
+        if batch_size < 1 {
+            return Err("Batch size must be positive!".to_owned().into());
+        }
+        let cursor = format!("dp_cursor_{}", CURSOR_COUNT.fetch_add(1, Ordering::Relaxed));
+        let query =
+            Self::select_query_builder(table_name, None, filter_list, sort_list, sort_type, None)?;
+        let query = format!("DECLARE {} NO SCROLL CURSOR FOR {}", cursor, query);
+        debug!("Declare {}", query);
+        transaction
+            .transaction
+            .execute(query.as_str(), filter_values)
+            .await?;
+        let fetch = format!("FETCH {} FROM {}", batch_size, cursor);
+        let close = format!("CLOSE {}", cursor);
+        Ok(stream::unfold(Some(()), move |state| {
+            let fetch = fetch.clone();
+            let close = close.clone();
+            async move {
+                state?;
+                debug!("Query {}", fetch);
+                let rows = match transaction.transaction.query(fetch.as_str(), &[]).await {
+                    Ok(rows) => rows,
+                    Err(e) => return Some((Err(e.into()), None)),
+                };
+                if rows.is_empty() {
+                    return match transaction.transaction.batch_execute(&close).await {
+                        Ok(_) => None,
+                        Err(e) => Some((Err(e.into()), None)),
+                    };
+                }
+                let rows = rows.iter().map(Self::parse_type).collect();
+                Some((rows, Some(())))
+            }
+        })
+        .boxed())
+    }
+
+    /// Like [`select_one`], but parse output to `RowType`
+    ///
+    /// [`select_one`]: #method.select_one
+    async fn select_one_typed<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+    ) -> Result<Self::RowType, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let row = Self::select_one(executor, table_name, None, filter_list, filter_values).await?;
+        Ok(Self::parse_type(&row)?)
+    }
+
+    /// Like [`select_opt`], but parse output to `RowType`
+    ///
+    /// [`select_opt`]: #method.select_opt
+    async fn select_opt_typed<E>(
+        executor: &E,
         table_name: Option<&str>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<Option<Self::RowType>, SQLError> {
-        match Self::select_opt(pool, table_name, None, filter_list, filter_values).await? {
+    ) -> Result<Option<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        match Self::select_opt(executor, table_name, None, filter_list, filter_values).await? {
             None => Ok(None),
             Some(row) => Ok(Some(Self::parse_type(&row)?)),
         }
     }
 
-    /// Run a `SELECT` query and return number of rows
-    async fn count(
-        pool: &PgPools,
+    /// Run a `SELECT COUNT(*)` query and return number of rows
+    async fn count<E>(
+        executor: &E,
         table_name: Option<&str>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<u64, SQLError> {
-        let query = Self::select_query_builder(table_name, None, filter_list, None, None);
-        Self::execute(pool, QueryType::RAW(query), filter_values, true).await
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let count: i64 = Self::aggregate(
+            executor,
+            table_name,
+            SQLAggregate::COUNT,
+            filter_list,
+            filter_values,
+        )
+        .await?;
+        Ok(count as u64)
     }
 
-    /// Run a `SELECT` query and return `true` if find any row(s)
-    async fn exists(
-        pool: &PgPools,
+    /// Run a `SELECT COUNT(DISTINCT field)` query and return number of distinct non-null values
+    async fn count_distinct<E>(
+        executor: &E,
         table_name: Option<&str>,
+        field_name: &str,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<bool, SQLError> {
-        Ok(
-            match Self::count(pool, table_name, filter_list, filter_values).await? {
-                0 => false,
-                _ => true,
-            },
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let count: i64 = Self::aggregate(
+            executor,
+            table_name,
+            SQLAggregate::COUNT_DISTINCT(field_name),
+            filter_list,
+            filter_values,
         )
+        .await?;
+        Ok(count as u64)
+    }
+
+    /// Run a `SELECT` query and return `true` if find any row(s)
+    async fn exists<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+    ) -> Result<bool, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        Ok(Self::count(executor, table_name, filter_list, filter_values).await? != 0)
     }
 
     /// Run a 'SELECT' query and return `true` if exactly find one row
-    async fn exists_one(
-        pool: &PgPools,
+    async fn exists_one<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+    ) -> Result<bool, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        Ok(Self::count(executor, table_name, filter_list, filter_values).await? == 1)
+    }
+
+    /// This function generates `SELECT aggregate AS name` query (e.g. `SELECT MIN("id") AS min ...`)
+    fn aggregate_query_builder(
+        table_name: Option<&str>,
+        aggregate: SQLAggregate<'_>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+    ) -> Result<String, SQLError> {
+        let table_name = Self::table_query_builder(table_name)?;
+        let filters = Self::filter_query_builder(filter_list, 0)?;
+        Ok(format!(
+            "SELECT {} AS {} FROM {} {}",
+            aggregate.to_query()?,
+            aggregate.name(),
+            table_name,
+            filters
+        ))
+    }
+
+    /// Calculate an aggregate (see `SQLAggregate`) of generic type `T` using a PostgreSQL `SELECT` query
+    ///
+    /// `T` must match the PostgreSQL type of the result (e.g. `SUM` of `integer` is `bigint`), and aggregates
+    /// (except `COUNT`) of no rows are `NULL`, so `T` can be an `Option`
+    async fn aggregate<T, E>(
+        executor: &E,
         table_name: Option<&str>,
+        aggregate: SQLAggregate<'_>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<bool, SQLError> {
+    ) -> Result<T, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        for<'b> T: FromSql<'b>,
+    {
+        let query = Self::aggregate_query_builder(table_name, aggregate, filter_list)?;
         Ok(
-            match Self::count(pool, table_name, filter_list, filter_values).await? {
-                1 => true,
-                _ => false,
-            },
+            Self::query_one(executor, QueryType::RAW(query), filter_values, true)
+                .await?
+                .try_get(0)?,
         )
     }
 
     /// Calculate SQL `MIN()` value of generic type `T` using a PostgreSQL `SELECT` query
-    async fn min<T>(
-        pool: &PgPools,
+    async fn min<T, E>(
+        executor: &E,
         table_name: Option<&str>,
         field_name: &str,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
     ) -> Result<T, SQLError>
     where
+        E: PgExecutor + ?Sized,
         for<'b> T: FromSql<'b>,
     {
-        Ok(Self::select_one(
-            pool,
+        Self::aggregate(
+            executor,
             table_name,
-            Some(vec![&format!("MIN({}) as min", field_name)]),
+            SQLAggregate::MIN(field_name),
             filter_list,
             filter_values,
         )
-        .await?
-        .get("min"))
+        .await
     }
 
     /// Calculate SQL `MAX()` value of generic type `T` using a PostgreSQL `SELECT` query
-    async fn max<T>(
-        pool: &PgPools,
+    async fn max<T, E>(
+        executor: &E,
         table_name: Option<&str>,
         field_name: &str,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
     ) -> Result<T, SQLError>
     where
+        E: PgExecutor + ?Sized,
         for<'b> T: FromSql<'b>,
     {
-        Ok(Self::select_one(
-            pool,
+        Self::aggregate(
+            executor,
             table_name,
-            Some(vec![&format!("MAX({}) as max", field_name)]),
+            SQLAggregate::MAX(field_name),
             filter_list,
             filter_values,
         )
-        .await?
-        .get("max"))
+        .await
+    }
+
+    /// Calculate SQL `SUM()` value of generic type `T` using a PostgreSQL `SELECT` query
+    /// (see [`aggregate`] for the type of `T`)
+    ///
+    /// [`aggregate`]: #method.aggregate
+    async fn sum<T, E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_name: &str,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+    ) -> Result<T, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        for<'b> T: FromSql<'b>,
+    {
+        Self::aggregate(
+            executor,
+            table_name,
+            SQLAggregate::SUM(field_name),
+            filter_list,
+            filter_values,
+        )
+        .await
+    }
+
+    /// Calculate SQL `AVG()` value using a PostgreSQL `SELECT` query; `None` if no rows are matched
+    async fn avg<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_name: &str,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<f64>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        Self::aggregate(
+            executor,
+            table_name,
+            SQLAggregate::AVG(field_name),
+            filter_list,
+            filter_values,
+        )
+        .await
+    }
+
+    /// This function generates `SELECT group_list, aggregates ... GROUP BY group_list HAVING ...` query;
+    /// `having_list` fields can be aliases of `aggregate_list`, and their values come after `filter_list` values
+    #[allow(clippy::too_many_arguments)]
+    fn group_query_builder(
+        table_name: Option<&str>,
+        group_list: &[&str],
+        aggregate_list: &[(SQLAggregate<'_>, &str)],
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        having_list: Option<Vec<SQLCondition<'_>>>,
+        sort_list: Option<Vec<SQLOrder<'_>>>,
+        sort_type: Option<SQLSort>,
+        limit: Option<SQLLimit>,
+    ) -> Result<String, SQLError> {
+        if group_list.is_empty() && aggregate_list.is_empty() {
+            return Err("No group or aggregate field find!".to_owned().into());
+        }
+        let table_name = Self::table_query_builder(table_name)?;
+        let groups = Self::identifier_query_builder(group_list)?;
+        let mut fields = groups.clone();
+        for (aggregate, alias) in aggregate_list {
+            fields.push(format!(
+                "{} AS {}",
+                aggregate.to_query()?,
+                SQLIdentifier::quote(alias)?
+            ));
+        }
+        let offset = filter_list
+            .as_ref()
+            .map_or(0, |items| items.iter().map(|item| item.param_count()).sum())
+            as i32;
+        let filters = Self::filter_query_builder(filter_list, 0)?;
+        let group_by = match groups.is_empty() {
+            true => "".to_owned(),
+            false => format!(" GROUP BY {} ", groups.join(", ")),
+        };
+        let having = Self::condition_query_builder("HAVING", having_list, offset, &|field| {
+            match aggregate_list.iter().find(|(_, alias)| *alias == field) {
+                Some((aggregate, _)) => aggregate.to_query(),
+                None => SQLIdentifier::quote(field),
+            }
+        })?;
+        let sorts = Self::sort_query_builder(sort_list, sort_type)?;
+        let limits = Self::limit_query_builder(limit);
+        Ok(format!(
+            "SELECT {} FROM {} {} {} {} {} {}",
+            fields.join(", "),
+            table_name,
+            filters,
+            group_by,
+            having,
+            sorts,
+            limits
+        ))
     }
 
     /// Calculate current value + `1` of generic integer type `T` using the [`max`] function
     ///
+    /// Concurrent calls can return the same value, so it should not be used for keys; see [`next_id`]
+    ///
     /// [`max`]: #method.max
-    async fn next<T>(
-        pool: &PgPools,
+    /// [`next_id`]: #method.next_id
+    #[deprecated(
+        note = "MAX + 1 is not safe under concurrency; use `next_id` or an identity column instead"
+    )]
+    async fn next<T, E>(
+        executor: &E,
         table_name: Option<&str>,
         field_name: &str,
     ) -> Result<T, SQLError>
     where
+        E: PgExecutor + ?Sized,
         for<'b> T: FromSql<'b> + Add<T, Output = T> + Copy + One,
     {
-        Ok(Self::max::<T>(pool, table_name, field_name, None, &[]).await? + One::one())
+        Ok(Self::max::<T, E>(executor, table_name, field_name, None, &[]).await? + One::one())
+    }
+
+    /// Returns the sequence of a `serial` or identity column (quoted if needed), using `pg_get_serial_sequence`
+    async fn sequence_name<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_name: &str,
+    ) -> Result<String, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let table_name = Self::table_query_builder(table_name)?;
+        let query = "SELECT pg_get_serial_sequence($1, $2)".to_owned();
+        let params: [&(dyn ToSql + Sync); 2] = [&table_name, &SQLIdentifier::name(field_name)?];
+        let sequence: Option<String> =
+            Self::query_one(executor, QueryType::RAW(query), &params, true)
+                .await?
+                .try_get(0)?;
+        sequence
+            .ok_or_else(|| format!("No sequence find for {}.{}!", table_name, field_name).into())
+    }
+
+    /// Returns the next value of the `sequence` (e.g. `public.example_table_id_seq`) using `nextval`
+    ///
+    /// Values are unique under concurrency, but they can have gaps (e.g. by rolled back transactions)
+    async fn nextval<E>(executor: &E, sequence: &str) -> Result<i64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        Ok(Self::nextval_many(executor, sequence, 1).await?[0])
+    }
+
+    /// Like [`nextval`], but reserves `count` values of the `sequence` in one query
+    /// (they are unique, but not always consecutive)
+    ///
+    /// [`nextval`]: #method.nextval
+    async fn nextval_many<E>(executor: &E, sequence: &str, count: i64) -> Result<Vec<i64>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query =
+            "SELECT nextval($1::text::regclass) FROM generate_series(1, $2::int8)".to_owned();
+        let sequence = SQLIdentifier::quote(sequence)?;
+        Self::query(executor, QueryType::RAW(query), &[&sequence, &count], false)
+            .await?
+            .iter()
+            .map(|row| Ok(row.try_get(0)?))
+            .collect()
+    }
+
+    /// Returns the value that [`nextval`] returned most recently for the `sequence` in the current session
+    ///
+    /// Sessions are connections, so it should run on the same `PgTransaction`, `PgPoolClient` or `PgClient`
+    /// as `nextval`; otherwise it can fail or return a value of another session
+    ///
+    /// [`nextval`]: #method.nextval
+    async fn currval<E>(executor: &E, sequence: &str) -> Result<i64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = "SELECT currval($1::text::regclass)".to_owned();
+        let sequence = SQLIdentifier::quote(sequence)?;
+        Ok(
+            Self::query_one(executor, QueryType::RAW(query), &[&sequence], false)
+                .await?
+                .try_get(0)?,
+        )
+    }
+
+    /// Sets the current value of the `sequence` using `setval`; if `is_called` is `false`, the next
+    /// [`nextval`] returns `value` itself, otherwise `value + 1`
+    ///
+    /// [`nextval`]: #method.nextval
+    async fn setval<E>(
+        executor: &E,
+        sequence: &str,
+        value: i64,
+        is_called: bool,
+    ) -> Result<i64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = "SELECT setval($1::text::regclass, $2, $3)".to_owned();
+        let sequence = SQLIdentifier::quote(sequence)?;
+        Ok(Self::query_one(
+            executor,
+            QueryType::RAW(query),
+            &[&sequence, &value, &is_called],
+            false,
+        )
+        .await?
+        .try_get(0)?)
+    }
+
+    /// Returns the next value of the sequence of a `serial` or identity column; it replaces [`next`]
+    ///
+    /// ```no_run
+    /// let id = ExampleTable::next_id(&pool, None, "id").await?;
+    /// ```
+    ///
+    /// [`next`]: #method.next
+    async fn next_id<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_name: &str,
+    ) -> Result<i64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        Ok(Self::next_ids(executor, table_name, field_name, 1).await?[0])
+    }
+
+    /// Like [`next_id`], but reserves `count` ids in one query (e.g. for `insert_many` or `copy_in` of a batch)
+    ///
+    /// [`next_id`]: #method.next_id
+    async fn next_ids<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_name: &str,
+        count: i64,
+    ) -> Result<Vec<i64>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let sequence = Self::sequence_name(executor, table_name, field_name).await?;
+        Self::nextval_many(executor, &sequence, count).await
+    }
+
+    /// Sets the sequence of a `serial` or identity column to the maximum value of the column, so the next
+    /// [`next_id`] (or a default value) does not conflict with rows inserted with explicit ids;
+    /// returns the next id
+    ///
+    /// [`next_id`]: #method.next_id
+    async fn sync_sequence<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_name: &str,
+    ) -> Result<i64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let sequence = Self::sequence_name(executor, table_name, field_name).await?;
+        let query = format!(
+            "SELECT setval($1::text::regclass, COALESCE(MAX({}), 0) + 1, false) FROM {}",
+            SQLIdentifier::quote(field_name)?,
+            Self::table_query_builder(table_name)?
+        );
+        Ok(
+            Self::query_one(executor, QueryType::RAW(query), &[&sequence], false)
+                .await?
+                .try_get(0)?,
+        )
+    }
+
+    /// This function generates `INSERT` query for one row of `value_count` values
+    fn insert_query_builder(
+        table_name: Option<&str>,
+        field_list: Option<Vec<&str>>,
+        value_count: usize,
+    ) -> Result<String, SQLError> {
+        Self::insert_many_query_builder(table_name, field_list, 1, value_count)
+    }
+
+    /// This function generates `INSERT` query for `row_count` rows of `value_count` values
+    /// (`VALUES ($1, $2), ($3, $4), ...`); `field_list = None` results [`insert_field_list`], and `identity` columns
+    /// in the field list add `OVERRIDING SYSTEM VALUE`
+    ///
+    /// [`insert_field_list`]: #method.insert_field_list
+    fn insert_many_query_builder(
+        table_name: Option<&str>,
+        field_list: Option<Vec<&str>>,
+        row_count: usize,
+        value_count: usize,
+    ) -> Result<String, SQLError> {
+        let query = format!("INSERT INTO {} ", Self::table_query_builder(table_name)?);
+        let row_vec: Vec<String> = (0..row_count)
+            .map(|row| {
+                let param_vec: Vec<String> = (1..value_count + 1)
+                    .map(|val| format!("${}", row * value_count + val))
+                    .collect();
+                format!("({})", param_vec.join(", "))
+            })
+            .collect();
+        let params = row_vec.join(", ");
+        match field_list.or_else(|| Self::insert_field_list()) {
+            None => Ok(format!("{}VALUES {}", query, params)),
+            Some(fields) => {
+                let identity_list = Self::columns()
+                    .iter()
+                    .filter(|column| column.identity)
+                    .map(|column| SQLIdentifier::name(column.name))
+                    .collect::<Result<Vec<String>, SQLError>>()?;
+                let overriding = fields.iter().any(|field| {
+                    SQLIdentifier::name(field).is_ok_and(|name| identity_list.contains(&name))
+                });
+                Ok(format!(
+                    "{}({}){} VALUES {}",
+                    query,
+                    Self::identifier_query_builder(&fields)?.join(", "),
+                    match overriding {
+                        true => " OVERRIDING SYSTEM VALUE",
+                        false => "",
+                    },
+                    params
+                ))
+            }
+        }
+    }
+
+    /// This function converts an optional vector of string to PostgreSQL `RETURNING` params;
+    /// `None` results `RETURNING *`
+    fn returning_query_builder(returning_list: Option<Vec<&str>>) -> Result<String, SQLError> {
+        Ok(format!(
+            " RETURNING {}",
+            Self::field_query_builder(returning_list)?
+        ))
     }
 
     /// Insert one row to PostgreSQL
-    async fn insert(
-        pool: &PgPools,
+    async fn insert<E>(
+        executor: &E,
         table_name: Option<&str>,
         field_list: Option<Vec<&str>>,
         values: &[&(dyn ToSql + Sync)],
-    ) -> Result<u64, SQLError> {
-        let table_name = match table_name {
-            None => Self::table_name(),
-            Some(name) => name,
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = Self::insert_query_builder(table_name, field_list, values.len())?;
+        Self::execute(executor, QueryType::RAW(query), values, false).await
+    }
+
+    /// Like [`insert`], but inserts [`insert_values`] of `row` to [`insert_field_list`] fields
+    ///
+    /// [`insert`]: #method.insert
+    /// [`insert_values`]: #method.insert_values
+    /// [`insert_field_list`]: #method.insert_field_list
+    async fn insert_entity<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        row: &Self::RowType,
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        Self::RowType: Sync,
+    {
+        let values = Self::insert_values(row)?;
+        Self::insert(executor, table_name, None, &values).await
+    }
+
+    /// Like [`insert`], but returns the inserted row (`RETURNING *` or `returning_list` fields) as `RowType`
+    ///
+    /// [`insert`]: #method.insert
+    async fn insert_returning<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_list: Option<Vec<&str>>,
+        values: &[&(dyn ToSql + Sync)],
+        returning_list: Option<Vec<&str>>,
+    ) -> Result<Self::RowType, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = format!(
+            "{}{}",
+            Self::insert_query_builder(table_name, field_list, values.len())?,
+            Self::returning_query_builder(returning_list)?
+        );
+        Self::query_one_typed(executor, QueryType::RAW(query), values, false).await
+    }
+
+    /// Insert one row to PostgreSQL and returns the value of `id_field` (e.g. a generated `serial` or identity key)
+    ///
+    /// ```no_run
+    /// let id: i64 = ExampleTable::insert_id(&pool, None, Some(vec!["name"]), &[&name], "id").await?;
+    /// ```
+    async fn insert_id<T, E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_list: Option<Vec<&str>>,
+        values: &[&(dyn ToSql + Sync)],
+        id_field: &str,
+    ) -> Result<T, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        for<'b> T: FromSql<'b>,
+    {
+        let query = format!(
+            "{} RETURNING {}",
+            Self::insert_query_builder(table_name, field_list, values.len())?,
+            SQLIdentifier::quote(id_field)?
+        );
+        Ok(
+            Self::query_one(executor, QueryType::RAW(query), values, false)
+                .await?
+                .try_get(0)?,
+        )
+    }
+
+    /// Like [`insert_entity`], but returns the value of the primary key (see [`primary_key`]),
+    /// which should be one column
+    ///
+    /// [`insert_entity`]: #method.insert_entity
+    /// [`primary_key`]: #method.primary_key
+    async fn insert_entity_id<T, E>(
+        executor: &E,
+        table_name: Option<&str>,
+        row: &Self::RowType,
+    ) -> Result<T, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        Self::RowType: Sync,
+        for<'b> T: FromSql<'b>,
+    {
+        let id_field = match Self::primary_key()[..] {
+            [id_field] => id_field,
+            _ => return Err("Primary key should be one column!".to_owned().into()),
         };
-        let mut query = format!("INSERT INTO {} ", table_name);
-        let param_vec: Vec<String> = (1..values.len() + 1)
-            .into_iter()
-            .map(|val| format!("${}", val))
+        let values = Self::insert_values(row)?;
+        Self::insert_id(executor, table_name, None, &values, id_field).await
+    }
+
+    /// Insert many rows to PostgreSQL using multi-row `INSERT` queries and return number of inserted rows
+    ///
+    /// Rows are split into chunks to stay under the PostgreSQL limit of 65535 params per query; each chunk is a
+    /// separate query, so pass a `PgTransaction` to insert all or nothing
+    ///
+    /// ```no_run
+    /// let rows: Vec<[&(dyn ToSql + Sync); 2]> = items.iter().map(|item| [&item.id as _, &item.name as _]).collect();
+    /// let rows: Vec<&[&(dyn ToSql + Sync)]> = rows.iter().map(|row| &row[..]).collect();
+    /// ExampleTable::insert_many(&pool, None, Some(vec!["id", "name"]), &rows).await?;
+    /// ```
+    async fn insert_many<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_list: Option<Vec<&str>>,
+        rows: &[&[&(dyn ToSql + Sync)]],
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let value_count = match rows.first() {
+            None => return Ok(0),
+            Some(row) => row.len(),
+        };
+        if value_count == 0 || value_count > MAX_QUERY_PARAMS {
+            return Err(format!("Invalid number of values in a row: {}!", value_count).into());
+        }
+        if rows.iter().any(|row| row.len() != value_count) {
+            return Err("All rows should have the same number of values!"
+                .to_owned()
+                .into());
+        }
+        let mut inserted = 0;
+        for chunk in rows.chunks(MAX_QUERY_PARAMS / value_count) {
+            let query = Self::insert_many_query_builder(
+                table_name,
+                field_list.clone(),
+                chunk.len(),
+                value_count,
+            )?;
+            let params = chunk.concat();
+            inserted += Self::execute(executor, QueryType::RAW(query), &params, false).await?;
+        }
+        Ok(inserted)
+    }
+
+    /// Bulk load rows to PostgreSQL using `COPY ... FROM STDIN (FORMAT binary)` and return number of copied rows
+    ///
+    /// Values of each row come from [`row_values`]: the values of `field_list` columns are picked by their names
+    /// (if no columns are set, all values are copied and should match `field_list`); if it is `None`, values come
+    /// from [`insert_values`] for [`insert_field_list`] fields (all table fields if no columns are set);
+    /// an iterator can be passed as `futures_util::stream::iter(rows)`
+    ///
+    /// The type should use `#[derive(Table)]` (or implement [`row_values`]), otherwise it returns an error
+    ///
+    /// ```no_run
+    /// ExampleTable::copy_in(&pool, None, Some(vec!["name"]), futures_util::stream::iter(rows)).await?;
+    /// ```
+    ///
+    /// [`row_values`]: #method.row_values
+    /// [`insert_values`]: #method.insert_values
+    /// [`insert_field_list`]: #method.insert_field_list
+    async fn copy_in<E, S>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_list: Option<Vec<&str>>,
+        rows: S,
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        S: Stream<Item = Self::RowType> + Send,
+    {
+        let table_name = Self::table_query_builder(table_name)?;
+        let is_default = field_list.is_none();
+        let field_list = field_list.or_else(|| Self::insert_field_list());
+        let fields = match &field_list {
+            Some(items) if !items.is_empty() => Self::identifier_query_builder(items)?.join(", "),
+            _ => "*".to_owned(),
+        };
+        let indexes = match (&field_list, Self::columns()) {
+            (Some(items), columns) if !is_default && !columns.is_empty() => Some(
+                items
+                    .iter()
+                    .map(|item| {
+                        let name = SQLIdentifier::name(item)?;
+                        columns
+                            .iter()
+                            .position(|column| {
+                                SQLIdentifier::name(column.name).is_ok_and(|column| column == name)
+                            })
+                            .ok_or_else(|| {
+                                format!("{} is not a column of {}!", item, Self::table_name())
+                                    .into()
+                            })
+                    })
+                    .collect::<Result<Vec<usize>, SQLError>>()?,
+            ),
+            _ => None,
+        };
+        let client = executor.acquire(false).await?;
+        let statement = client
+            .statement(&format!("SELECT {} FROM {} LIMIT 0", fields, table_name))
+            .await?;
+        let types: Vec<_> = statement
+            .columns()
+            .iter()
+            .map(|column| column.type_().clone())
             .collect();
-        let params = param_vec.join(", ");
-        match field_list {
-            None => query = format!("{} VALUES ({});", query, params),
-            Some(fields) => {
-                query = format!("{} ({}) VALUES ({});", query, fields.join(", "), params)
+        let query = match field_list {
+            None => format!("COPY {} FROM STDIN (FORMAT binary)", table_name),
+            Some(_) => format!(
+                "COPY {} ({}) FROM STDIN (FORMAT binary)",
+                table_name, fields
+            ),
+        };
+        debug!("Copy in {}", query);
+        let sink = client.copy_in(&query).await?;
+        let writer = BinaryCopyInWriter::new(sink, &types);
+        pin_mut!(writer);
+        pin_mut!(rows);
+        while let Some(row) = rows.next().await {
+            let values = match (&indexes, is_default) {
+                (Some(indexes), _) => {
+                    let values = Self::row_values(&row)?;
+                    indexes
+                        .iter()
+                        .filter_map(|index| values.get(*index).copied())
+                        .collect()
+                }
+                (None, true) => Self::insert_values(&row)?,
+                (None, false) => Self::row_values(&row)?,
+            };
+            if values.len() != types.len() {
+                return Err(format!(
+                    "Row has {} values, but {} fields are copied!",
+                    values.len(),
+                    types.len()
+                )
+                .into());
+            }
+            writer.as_mut().write(&values).await?;
+        }
+        Ok(writer.finish().await?)
+    }
+
+    /// This function generates `INSERT ... ON CONFLICT` query; insert values come first, then filter values
+    /// of the `DO UPDATE ... WHERE` branch, whose unqualified fields are qualified with the table name
+    fn upsert_query_builder(
+        table_name: Option<&str>,
+        field_list: Vec<&str>,
+        conflict_list: Vec<&str>,
+        action: SQLConflict<'_>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+    ) -> Result<String, SQLError> {
+        if conflict_list.is_empty() {
+            return Err("No conflict field find!".to_owned().into());
+        }
+        let insert =
+            Self::insert_query_builder(table_name, Some(field_list.clone()), field_list.len())?;
+        let conflict = Self::identifier_query_builder(&conflict_list)?.join(", ");
+        let update_list = match action {
+            SQLConflict::NOTHING => {
+                if filter_list.is_some() {
+                    return Err("Conflict filters need SQLConflict::UPDATE!"
+                        .to_owned()
+                        .into());
+                }
+                return Ok(format!("{} ON CONFLICT ({}) DO NOTHING", insert, conflict));
             }
+            SQLConflict::UPDATE(Some(fields)) => fields,
+            SQLConflict::UPDATE(None) => field_list
+                .iter()
+                .filter(|field| !conflict_list.contains(field))
+                .copied()
+                .collect(),
         };
-        Self::execute(pool, QueryType::RAW(query), values, false).await
+        if update_list.is_empty() {
+            return Err("No update field find!".to_owned().into());
+        }
+        let updates: Vec<String> = Self::identifier_query_builder(&update_list)?
+            .into_iter()
+            .map(|field| format!("{} = EXCLUDED.{}", field, field))
+            .collect();
+        let table = Self::table_query_builder(table_name)?;
+        let filters = Self::condition_query_builder(
+            "WHERE",
+            filter_list,
+            field_list.len() as i32,
+            &|field| {
+                let field = SQLIdentifier::quote(field)?;
+                match field.contains("\".\"") {
+                    true => Ok(field),
+                    false => Ok(format!("{}.{}", table, field)),
+                }
+            },
+        )?;
+        Ok(format!(
+            "{} ON CONFLICT ({}) DO UPDATE SET {} {}",
+            insert,
+            conflict,
+            updates.join(", "),
+            filters
+        ))
+    }
+
+    /// Insert one row to PostgreSQL, or run `action` if it conflicts with an existing row on `conflict_list`
+    ///
+    /// `filter_list` adds a `WHERE` to the `DO UPDATE` branch; its values come after `values`, and its fields are
+    /// columns of the existing row, unless they are qualified (e.g. `excluded.total` for the inserted row)
+    ///
+    /// ```no_run
+    /// ExampleTable::upsert(&pool, None, vec!["id", "name"], &[&id, &name], vec!["id"], SQLConflict::UPDATE(None), None, &[]).await?;
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    async fn upsert<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        field_list: Vec<&str>,
+        values: &[&(dyn ToSql + Sync)],
+        conflict_list: Vec<&str>,
+        action: SQLConflict<'_>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query =
+            Self::upsert_query_builder(table_name, field_list, conflict_list, action, filter_list)?;
+        let params = [values, filter_values].concat();
+        Self::execute(executor, QueryType::RAW(query), &params, false).await
+    }
+
+    /// This function generates `DELETE` query
+    fn delete_query_builder(
+        table_name: Option<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+    ) -> Result<String, SQLError> {
+        let table_name = Self::table_query_builder(table_name)?;
+        let filters = Self::filter_query_builder(filter_list, 0)?;
+        Ok(format!("DELETE FROM {} {}", table_name, filters))
     }
 
     /// Running `DELETE` query based on provided conditions
-    async fn delete(
-        pool: &PgPools,
+    async fn delete<E>(
+        executor: &E,
         table_name: Option<&str>,
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<u64, SQLError> {
-        let table_name = match table_name {
-            None => Self::table_name(),
-            Some(name) => name,
-        };
-        let filters = Self::filter_query_builder(filter_list, 0);
-        let query = format!("DELETE FROM {} {}", table_name, filters);
-        Self::execute(pool, QueryType::RAW(query), filter_values, false).await
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = Self::delete_query_builder(table_name, filter_list)?;
+        Self::execute(executor, QueryType::RAW(query), filter_values, false).await
+    }
+
+    /// Like [`delete`], but returns the deleted rows (`RETURNING *` or `returning_list` fields) as `RowType`
+    ///
+    /// [`delete`]: #method.delete
+    async fn delete_returning<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+        returning_list: Option<Vec<&str>>,
+    ) -> Result<Vec<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = format!(
+            "{}{}",
+            Self::delete_query_builder(table_name, filter_list)?,
+            Self::returning_query_builder(returning_list)?
+        );
+        Self::query_typed(executor, QueryType::RAW(query), filter_values, false).await
     }
 
     /// Generating a list of SQL update field based on a vector of string
-    fn update_query_builder(update_list: Vec<&str>, offset: i32) -> (i32, String) {
-        if update_list.len() == 0 {
-            return (0, "".to_owned());
+    fn update_query_builder(
+        update_list: Vec<&str>,
+        offset: i32,
+    ) -> Result<(i32, String), SQLError> {
+        if update_list.is_empty() {
+            Ok((0, "".to_owned()))
         } else {
             let mut index = offset;
-            let list: Vec<String> = update_list
+            let list: Vec<String> = Self::identifier_query_builder(&update_list)?
                 .into_iter()
                 .map(|item| {
                     index += 1;
                     format!("{} = ${}", item, index)
                 })
                 .collect();
-            return (index, list.join(", "));
+            Ok((index, list.join(", ")))
         }
     }
 
+    /// This function generates `UPDATE` query; update values come first, then filter values
+    fn update_statement_builder(
+        table_name: Option<&str>,
+        update_list: Vec<&str>,
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+    ) -> Result<String, SQLError> {
+        let table_name = Self::table_query_builder(table_name)?;
+        if update_list.is_empty() {
+            return Err("No update field find!".to_owned().into());
+        }
+        let (offset, lists) = Self::update_query_builder(update_list, 0)?;
+        let filters = Self::filter_query_builder(filter_list, offset)?;
+        Ok(format!("UPDATE {} SET {} {}", table_name, lists, filters))
+    }
+
     /// Running `UPDATE` query based on provided params
-    async fn update(
-        pool: &PgPools,
+    async fn update<E>(
+        executor: &E,
         table_name: Option<&str>,
         update_list: Vec<&str>,
         update_values: &[&(dyn ToSql + Sync)],
         filter_list: Option<Vec<SQLCondition<'_>>>,
         filter_values: &[&(dyn ToSql + Sync)],
-    ) -> Result<u64, SQLError> {
-        let table_name = match table_name {
-            None => Self::table_name(),
-            Some(name) => name,
-        };
-        if update_list.len() == 0 {
-            return Err("No update field find!".to_owned().into());
-        }
-        let (offset, lists) = Self::update_query_builder(update_list, 0);
-        let filters = Self::filter_query_builder(filter_list, offset);
-        let query = format!("UPDATE {} SET {} {}", table_name, lists, filters);
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = Self::update_statement_builder(table_name, update_list, filter_list)?;
         let params = [update_values, filter_values].concat();
-        Self::execute(pool, QueryType::RAW(query), &params, false).await
+        Self::execute(executor, QueryType::RAW(query), &params, false).await
+    }
+
+    /// Like [`update`], but returns the updated rows (`RETURNING *` or `returning_list` fields) as `RowType`
+    ///
+    /// [`update`]: #method.update
+    async fn update_returning<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        update_list: Vec<&str>,
+        update_values: &[&(dyn ToSql + Sync)],
+        filter_list: Option<Vec<SQLCondition<'_>>>,
+        filter_values: &[&(dyn ToSql + Sync)],
+        returning_list: Option<Vec<&str>>,
+    ) -> Result<Vec<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        let query = format!(
+            "{}{}",
+            Self::update_statement_builder(table_name, update_list, filter_list)?,
+            Self::returning_query_builder(returning_list)?
+        );
+        let params = [update_values, filter_values].concat();
+        Self::query_typed(executor, QueryType::RAW(query), &params, false).await
+    }
+
+    /// This function generates `WHERE` conditions of [`primary_key`] fields (`key1 = $1 AND key2 = $2 ...`)
+    ///
+    /// [`primary_key`]: #method.primary_key
+    fn key_filter_list() -> Result<Vec<SQLCondition<'static>>, SQLError> {
+        let keys = Self::primary_key();
+        if keys.is_empty() {
+            return Err("No primary key find!".to_owned().into());
+        }
+        Ok(vec![SQLCondition::all(
+            keys.into_iter().map(SQLCondition::EQUAL).collect(),
+        )])
+    }
+
+    /// This function returns each of [`columns`] with its value of a row (from [`row_values`])
+    ///
+    /// [`columns`]: #method.columns
+    /// [`row_values`]: #method.row_values
+    fn column_values(
+        row: &Self::RowType,
+    ) -> Result<Vec<(&'static SQLColumn, &(dyn ToSql + Sync))>, SQLError> {
+        let columns = Self::columns();
+        if columns.is_empty() {
+            return Err(format!(
+                "columns are not implemented for {}! (use #[derive(Table)])",
+                Self::table_name()
+            )
+            .into());
+        }
+        Ok(columns.iter().zip(Self::row_values(row)?).collect())
+    }
+
+    /// This function returns values of [`primary_key`] columns of a row (from [`column_values`])
+    ///
+    /// [`primary_key`]: #method.primary_key
+    /// [`column_values`]: #method.column_values
+    fn key_values(row: &Self::RowType) -> Result<Vec<&(dyn ToSql + Sync)>, SQLError> {
+        let keys = Self::primary_key();
+        let column_values = Self::column_values(row)?;
+        let values: Vec<&(dyn ToSql + Sync)> = keys
+            .iter()
+            .filter_map(|key| {
+                column_values
+                    .iter()
+                    .find(|(column, _)| column.name == *key)
+                    .map(|(_, value)| *value)
+            })
+            .collect();
+        if keys.is_empty() || values.len() != keys.len() {
+            return Err("No primary key column find!".to_owned().into());
+        }
+        Ok(values)
+    }
+
+    /// Find a row by its [`primary_key`] values
+    ///
+    /// ```no_run
+    /// let item = ExampleTable::find_by_id(&pool, None, &[&id]).await?;
+    /// ```
+    ///
+    /// [`primary_key`]: #method.primary_key
+    async fn find_by_id<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        id: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<Self::RowType>, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        Self::select_opt_typed(executor, table_name, Some(Self::key_filter_list()?), id).await
+    }
+
+    /// Update all columns of a row, except its [`primary_key`] and `skip_insert` columns, by its primary key
+    ///
+    /// [`primary_key`]: #method.primary_key
+    async fn update_entity<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        row: &Self::RowType,
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        Self::RowType: Sync,
+    {
+        let filter_list = Self::key_filter_list()?;
+        let keys = Self::primary_key();
+        let (update_list, update_values): (Vec<&str>, Vec<&(dyn ToSql + Sync)>) =
+            Self::column_values(row)?
+                .into_iter()
+                .filter(|(column, _)| !keys.contains(&column.name) && !column.skip_insert)
+                .map(|(column, value)| (column.name, value))
+                .unzip();
+        let filter_values = Self::key_values(row)?;
+        Self::update(
+            executor,
+            table_name,
+            update_list,
+            &update_values,
+            Some(filter_list),
+            &filter_values,
+        )
+        .await
+    }
+
+    /// Delete a row by its [`primary_key`] values
+    ///
+    /// [`primary_key`]: #method.primary_key
+    async fn delete_by_id<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        id: &[&(dyn ToSql + Sync)],
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+    {
+        Self::delete(executor, table_name, Some(Self::key_filter_list()?), id).await
+    }
+
+    /// Insert a row, or update its columns if its [`primary_key`] exists (`INSERT ... ON CONFLICT DO UPDATE`)
+    ///
+    /// Primary key columns are always inserted (even if they are `skip_insert`), so the key of the row is used
+    ///
+    /// [`primary_key`]: #method.primary_key
+    async fn save<E>(
+        executor: &E,
+        table_name: Option<&str>,
+        row: &Self::RowType,
+    ) -> Result<u64, SQLError>
+    where
+        E: PgExecutor + ?Sized,
+        Self::RowType: Sync,
+    {
+        let keys = Self::primary_key();
+        let (field_list, values): (Vec<&str>, Vec<&(dyn ToSql + Sync)>) = Self::column_values(row)?
+            .into_iter()
+            .filter(|(column, _)| keys.contains(&column.name) || !column.skip_insert)
+            .map(|(column, value)| (column.name, value))
+            .unzip();
+        if keys.is_empty() || keys.iter().any(|key| !field_list.contains(key)) {
+            return Err("No primary key column find!".to_owned().into());
+        }
+        Self::upsert(
+            executor,
+            table_name,
+            field_list,
+            &values,
+            keys,
+            SQLConflict::UPDATE(None),
+            None,
+            &[],
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use postgres_types::{FromSql, ToSql};
+
+    #[derive(Debug, FromRow, ToSql, FromSql, Serialize)]
+    struct Item {
+        id: i64,
+    }
+
+    impl DPQueryable<'_> for Item {
+        type RowType = Self;
+        fn table_name() -> &'static str {
+            "public.items"
+        }
+    }
+
+    #[derive(Debug, FromRow, ToSql, FromSql, Serialize)]
+    struct IdentItem {
+        id: i64,
+        name: String,
+    }
+
+    impl DPQueryable<'_> for IdentItem {
+        type RowType = Self;
+        fn table_name() -> &'static str {
+            "public.ident_items"
+        }
+        fn columns() -> &'static [SQLColumn] {
+            &[
+                SQLColumn {
+                    name: "id",
+                    field: "id",
+                    primary_key: true,
+                    skip_insert: true,
+                    identity: true,
+                },
+                SQLColumn {
+                    name: "name",
+                    field: "name",
+                    primary_key: false,
+                    skip_insert: false,
+                    identity: false,
+                },
+            ]
+        }
+    }
+
+    #[test]
+    fn insert_many_fields() {
+        assert_eq!(
+            IdentItem::insert_many_query_builder(None, None, 2, 1).unwrap(),
+            r#"INSERT INTO "public"."ident_items" ("name") VALUES ($1), ($2)"#
+        );
+        assert_eq!(
+            Item::insert_many_query_builder(None, None, 1, 1).unwrap(),
+            r#"INSERT INTO "public"."items" VALUES ($1)"#
+        );
+    }
+
+    #[test]
+    fn insert_many_identity() {
+        assert_eq!(
+            IdentItem::insert_many_query_builder(None, Some(vec!["ID", "name"]), 1, 2).unwrap(),
+            r#"INSERT INTO "public"."ident_items" ("id", "name") OVERRIDING SYSTEM VALUE VALUES ($1, $2)"#
+        );
+        assert!(
+            !IdentItem::insert_many_query_builder(None, Some(vec![r#""Id""#]), 1, 1)
+                .unwrap()
+                .contains("OVERRIDING")
+        );
+    }
+
+    #[test]
+    fn param_literals() {
+        let literals = vec!["'a'::text".to_owned(), "2::int8".to_owned()];
+        let query = |query: &str| Item::param_literal_query_builder(query, &literals).unwrap();
+        assert_eq!(
+            query("SELECT * FROM t WHERE a = $1 AND b IN ($2, $1)"),
+            "SELECT * FROM t WHERE a = ('a'::text) AND b IN ((2::int8), ('a'::text))"
+        );
+        assert_eq!(
+            query("SELECT '$1', 'it''s $2', \"$1\", E'\\' $1', a$1 FROM t WHERE b = $2"),
+            "SELECT '$1', 'it''s $2', \"$1\", E'\\' $1', a$1 FROM t WHERE b = (2::int8)"
+        );
+        assert_eq!(
+            query("SELECT $1 -- $2\n/* $1 /* $2 */ $1 */ $2"),
+            "SELECT ('a'::text) -- $2\n/* $1 /* $2 */ $1 */ (2::int8)"
+        );
+        assert_eq!(
+            query("SELECT $$ $1 $$, $tag$ $2 $$ $tag$, $2"),
+            "SELECT $$ $1 $$, $tag$ $2 $$ $tag$, (2::int8)"
+        );
+        assert_eq!(query("SELECT '$1"), "SELECT '$1");
+        assert!(Item::param_literal_query_builder("SELECT $3", &literals).is_err());
+    }
+
+    /// Collapses the spaces of a generated query, which builders pad around each clause
+    fn squash(query: Result<String, SQLError>) -> String {
+        query
+            .unwrap()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn upsert_update_filter() {
+        assert_eq!(
+            squash(Item::upsert_query_builder(
+                None,
+                vec!["id", "total"],
+                vec!["id"],
+                SQLConflict::UPDATE(None),
+                Some(vec![
+                    SQLCondition::LESS("total"),
+                    SQLCondition::AND,
+                    SQLCondition::NEQ("excluded.total"),
+                ]),
+            )),
+            r#"INSERT INTO "public"."items" ("id", "total") VALUES ($1, $2) ON CONFLICT ("id") DO UPDATE SET "total" = EXCLUDED."total" WHERE "public"."items"."total" < $3 AND "excluded"."total" <> $4"#
+        );
+        assert_eq!(
+            Item::upsert_query_builder(None, vec!["id"], vec!["id"], SQLConflict::NOTHING, None)
+                .unwrap(),
+            r#"INSERT INTO "public"."items" ("id") VALUES ($1) ON CONFLICT ("id") DO NOTHING"#
+        );
+        assert!(Item::upsert_query_builder(
+            None,
+            vec!["id"],
+            vec!["id"],
+            SQLConflict::NOTHING,
+            Some(vec![SQLCondition::ISNULL("id")])
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn keyset_same_direction() {
+        let sort_list = vec![SQLOrder::from("created_at"), SQLOrder::from("id")];
+        assert_eq!(
+            Item::keyset_query_builder(&sort_list, Some(SQLSort::DESC), 2).unwrap(),
+            r#"("created_at", "id") < ($3, $4)"#
+        );
+    }
+
+    #[test]
+    fn keyset_mixed_direction() {
+        let sort_list = vec![SQLOrder::desc("Score"), SQLOrder::asc("id")];
+        assert_eq!(
+            Item::keyset_query_builder(&sort_list, None, 0).unwrap(),
+            r#"(("score" < $1) OR ("score" = $1 AND "id" > $2))"#
+        );
+    }
+
+    #[test]
+    fn keyset_rejects_identifier() {
+        let sort_list = vec![SQLOrder::from("id; DROP TABLE items")];
+        assert!(Item::keyset_query_builder(&sort_list, None, 0).is_err());
+    }
+
+    #[test]
+    fn key_values_without_columns() {
+        let error = Item::key_values(&Item { id: 1 }).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "columns are not implemented for public.items! (use #[derive(Table)])"
+        );
+    }
+
+    #[test]
+    fn filter_params() {
+        assert_eq!(
+            squash(Item::filter_query_builder(
+                Some(vec![
+                    SQLCondition::ISNULL("deleted_at"),
+                    SQLCondition::AND,
+                    SQLCondition::BETWEEN("total"),
+                    SQLCondition::AND,
+                    SQLCondition::NOTNULL("name"),
+                    SQLCondition::AND,
+                    SQLCondition::IN("id"),
+                    SQLCondition::AND,
+                    SQLCondition::ILIKE("name"),
+                ]),
+                1,
+            )),
+            r#"WHERE "deleted_at" IS NULL AND "total" BETWEEN $2 AND $3 AND "name" IS NOT NULL AND "id" = ANY($4) AND "name" ILIKE $5"#
+        );
+        assert_eq!(Item::filter_query_builder(Some(vec![]), 0).unwrap(), "");
+    }
+
+    #[test]
+    fn filter_nested_params() {
+        assert_eq!(
+            squash(Item::filter_query_builder(
+                Some(vec![
+                    SQLCondition::EQUAL("id"),
+                    SQLCondition::AND,
+                    SQLCondition::NOT(vec![
+                        SQLCondition::GROUP(vec![
+                            SQLCondition::BETWEEN("total"),
+                            SQLCondition::OR,
+                            SQLCondition::ISNULL("total"),
+                        ]),
+                        SQLCondition::AND,
+                        SQLCondition::LIKE("name"),
+                    ]),
+                    SQLCondition::OR,
+                    SQLCondition::GREATER("score"),
+                ]),
+                0,
+            )),
+            r#"WHERE "id" = $1 AND NOT ( ( "total" BETWEEN $2 AND $3 OR "total" IS NULL ) AND "name" LIKE $4 ) OR "score" > $5"#
+        );
+        assert!(Item::filter_query_builder(Some(vec![SQLCondition::GROUP(vec![])]), 0).is_err());
+    }
+
+    #[test]
+    fn update_filter_offset() {
+        assert_eq!(
+            squash(Item::update_statement_builder(
+                None,
+                vec!["name", "total"],
+                Some(vec![
+                    SQLCondition::GROUP(vec![
+                        SQLCondition::NOTNULL("name"),
+                        SQLCondition::OR,
+                        SQLCondition::NOTIN("id"),
+                    ]),
+                    SQLCondition::AND,
+                    SQLCondition::BETWEEN("total"),
+                ]),
+            )),
+            r#"UPDATE "public"."items" SET "name" = $1, "total" = $2 WHERE ( "name" IS NOT NULL OR "id" <> ALL($3) ) AND "total" BETWEEN $4 AND $5"#
+        );
+        assert!(Item::update_statement_builder(None, vec![], None).is_err());
     }
 }
@@ -0,0 +1,86 @@
+use crate::common::SQLError;
+use crate::query_lib::SQLQueryLib;
+use async_trait::async_trait;
+use deadpool_postgres::{Client, StatementCache};
+use std::ops::Deref;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio_postgres::Statement;
+
+/// This `enum` holds a PostgreSQL connection for the duration of one operation
+///
+/// `Pooled(client)` is a client taken from one of `PgPools` pools; it goes back to the pool on drop
+///
+/// `Borrowed(client, cache)` is the connection of a `PgPoolClient` or an open `PgTransaction` and its statement cache
+///
+/// `Direct(client, handle)` is a new connection opened by `PgClient`; it is closed on drop
+pub enum PgConnection<'a> {
+    Pooled(Box<Client>),
+    Borrowed(&'a tokio_postgres::Client, &'a StatementCache),
+    Direct(tokio_postgres::Client, JoinHandle<()>),
+}
+
+impl PgConnection<'_> {
+    /// Creates a prepared statement on this connection, reading the statement cache first (if any)
+    pub async fn statement(&self, query: &str) -> Result<Statement, SQLError> {
+        match self {
+            Self::Pooled(client) => Ok(client.prepare_cached(query).await?),
+            Self::Borrowed(client, cache) => Ok(cache.prepare(client, query).await?),
+            Self::Direct(client, _) => Ok(client.prepare(query).await?),
+        }
+    }
+}
+
+impl Deref for PgConnection<'_> {
+    type Target = tokio_postgres::Client;
+
+    fn deref(&self) -> &tokio_postgres::Client {
+        match self {
+            Self::Pooled(client) => client,
+            Self::Borrowed(client, _) => client,
+            Self::Direct(client, _) => client,
+        }
+    }
+}
+
+impl Drop for PgConnection<'_> {
+    fn drop(&mut self) {
+        if let Self::Direct(_, handle) = self {
+            handle.abort();
+        }
+    }
+}
+
+/// This is an async trait for anything that can run PostgreSQL queries
+///
+/// It is implemented by `PgPools`, `PgPoolClient`, `PgTransaction` and `PgClient`, and all `DPQueryable`
+/// (and `Queryable`) functions accept any of them
+#[async_trait]
+pub trait PgExecutor: Sync {
+    /// Path of the folder that `QueryType::LIB` queries are read from
+    fn query_lib_path(&self) -> &str;
+
+    /// Returns the cached queries of [`query_lib_path`] (see `SQLQueryLib`) without reading any file; it can be
+    /// called at startup to check that the query lib is loaded
+    ///
+    /// [`query_lib_path`]: #tymethod.query_lib_path
+    fn query_lib(&self) -> Result<Arc<SQLQueryLib>, SQLError> {
+        SQLQueryLib::cached(self.query_lib_path())
+    }
+
+    /// Reads the SQL files of [`query_lib_path`] again and replaces the cached queries (see `SQLQueryLib::reload`),
+    /// e.g. to fail at startup if they can not be loaded, or after changing them
+    ///
+    /// ```no_run
+    /// let pool = PgPools::new(...);
+    /// pool.load_query_lib()?;
+    /// ```
+    ///
+    /// [`query_lib_path`]: #tymethod.query_lib_path
+    fn load_query_lib(&self) -> Result<Arc<SQLQueryLib>, SQLError> {
+        SQLQueryLib::reload(self.query_lib_path())
+    }
+
+    /// Returns a connection to run one operation on; `is_read_only` may be ignored (e.g. in a transaction)
+    async fn acquire(&self, is_read_only: bool) -> Result<PgConnection<'_>, SQLError>;
+}
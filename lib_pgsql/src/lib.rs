@@ -2,7 +2,25 @@
 //!
 //! `PgPools` provides a seprated read/write pool connection
 //!
-//! `Queryable` is an async trait that can connect a general struct to PostgreSQL
+//! `PgPoolClient::begin` starts a `PgTransaction` (rolled back if it is dropped without `commit`), and
+//! `PgPools::transaction` runs a closure inside one
+//!
+//! `DPQueryable` (also exported as `Queryable`) is an async trait that can connect a general struct to PostgreSQL
+//!
+//! `Select`, `Insert`, `Update` and `Delete` are fluent query builders (e.g. `ExampleTable::select_builder()`);
+//! `Select` also supports `INNER JOIN` and `LEFT JOIN` (see `SQLJoin`), and `GroupBy` runs `GROUP BY` queries
+//! with aggregates (see `SQLAggregate`)
+//!
+//! `#[derive(Table)]` implements `DPQueryable` with its column list for a struct
+//!
+//! `SQLRetry` runs an operation (or `PgPools::transaction_retry` a transaction) again on serialization failures,
+//! deadlocks and (for read-only operations) connection errors
+//!
+//! `QueryType::LIB` queries are read from `SQLQueryLib`, which caches the SQL files (and their `-- name: ...`
+//! queries) of the query lib folder
+//!
+//! `PgExecutor` is implemented by `PgPools`, `PgPoolClient`, `PgTransaction` and `PgClient`; any of them can run
+//! `DPQueryable` functions
 //!
 //! ```no_run
 //! use postgres_from_row::FromRow;
@@ -34,7 +52,7 @@
 //!         "5432", // Host port for write pool
 //!         "/SQL", // Path that SQL files are stored in server
 //!     );
-//!     let results = ExampleTable::select_typed(&pool, None, None, None, &[], None, None).await?;
+//!     let results = ExampleTable::select_typed(&pool, None, None, &[], None, None, None).await?;
 //!     println!("{:#?}", results);
 //! }
 //! ```
@@ -55,24 +73,47 @@
 //! postgres-types = { version = "", features = ["derive"] }
 //!```
 
+/// This module provides fluent `SELECT`, `GROUP BY`, `INSERT`, `UPDATE` and `DELETE` query builders
+pub mod builder;
 /// This module provide client for conncting to PostgreSQL
 pub mod client;
 /// This module provides common `enum` and `struct` for PostgreSQL operations
 pub mod common;
 /// This module provides an async trait for PostgreSQL operations for Rust structs (using deadpool)
 pub mod dpqueryable;
+/// This module provides an async trait for anything that can run PostgreSQL queries (pools, clients and transactions)
+pub mod executor;
 /// This module provides libraries and functions to generate Dead Pool PostgreSQL connection pools
 pub mod pool;
-/// This module provides an async trait for PostgreSQL operations for Rust structs (using tokio-postgres)
+/// This module provides a cache of SQL files and named queries of the query lib folder (`QueryType::LIB`)
+pub mod query_lib;
+/// This module exports `DPQueryable` as `Queryable` for PostgreSQL operations with `PgClient` (using tokio-postgres)
 pub mod queryable;
+/// This module provides retrying of operations on transient errors (e.g. serialization failures and deadlocks)
+pub mod retry;
+/// This module provides PostgreSQL transactions that can be passed to `DPQueryable` functions
+pub mod transaction;
 
+pub use builder::{Delete, GroupBy, Insert, Select, Update};
 pub use client::PgClient;
-pub use common::{QueryType, SQLCondition, SQLError, SQLSort};
+pub use common::{
+    QueryType, SQLAggregate, SQLColumn, SQLCondition, SQLConflict, SQLCursor, SQLError,
+    SQLErrorKind, SQLFormat, SQLIdentifier, SQLIsNull, SQLJoin, SQLJoinType, SQLKeysetPage,
+    SQLLimit, SQLNulls, SQLOrder, SQLPage, SQLSort,
+};
+pub use dpqueryable::DPQueryable;
+pub use executor::{PgConnection, PgExecutor};
 pub use futures_util::pin_mut;
-pub use pool::PgPools;
+pub use lib_pgsql_derive::Table;
+pub use pool::{PgPoolClient, PgPools};
 pub use postgres_from_row::FromRow;
+pub use query_lib::SQLQueryLib;
 pub use queryable::Queryable;
+pub use retry::{SQLRetry, SQLRetryHook};
 pub use serde::{Deserialize, Serialize};
 pub use tokio;
 pub use tokio::spawn;
+pub use tokio_postgres::error::{DbError, SqlState};
 pub use tokio_postgres::types::{FromSql, ToSql};
+pub use tokio_postgres::Row;
+pub use transaction::PgTransaction;
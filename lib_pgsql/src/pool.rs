@@ -1,6 +1,12 @@
-use std::usize;
-
-use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
+use crate::common::SQLError;
+use crate::executor::{PgConnection, PgExecutor};
+use crate::query_lib::SQLQueryLib;
+use crate::retry::SQLRetry;
+use crate::transaction::PgTransaction;
+use async_trait::async_trait;
+use deadpool_postgres::{Client, Manager, ManagerConfig, Pool, RecyclingMethod};
+use futures_util::future::BoxFuture;
+use log::warn;
 use tokio_postgres::NoTls;
 
 /// This struct provides read/write pools for PostgreSQL and path of query libraris
@@ -13,6 +19,9 @@ pub struct PgPools {
 impl PgPools {
     /// This function generates the PostgreSQL pools based on provided settings
     ///
+    /// SQL files of `lib_path` are loaded here, if they are not loaded before (see `SQLQueryLib::init`); a load error
+    /// is only logged, and `pool.query_lib()?` returns it (or `pool.load_query_lib()?` reads the files again)
+    ///
     /// ```no_run
     /// let pool = PgPools::new(
     ///    "postgres", // PostgreSQL username
@@ -27,6 +36,7 @@ impl PgPools {
     ///    "/SQL", // Path that SQL files are stored in server
     /// );
     /// ```
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user: &str,
         pass: &str,
@@ -59,6 +69,9 @@ impl PgPools {
             recycling_method: RecyclingMethod::Clean,
         };
         let pg_write_mgr = Manager::from_config(pg_write_config, NoTls, pg_write_mgr_cfg);
+        if let Err(e) = SQLQueryLib::init(&lib_path) {
+            warn!("Query lib {} is not loaded: {}", lib_path, e);
+        }
         Self {
             read_pool: Pool::builder(pg_read_mgr)
                 .max_size(read_pool_size)
@@ -85,4 +98,161 @@ impl PgPools {
             &self.write_pool
         }
     }
+
+    /// This function takes a client from either a read (if `is_read_only = true`) or write pool,
+    /// so several operations can run on the same connection
+    ///
+    /// ```no_run
+    /// let client = pool.client(true).await?;
+    /// let rows = ExampleTable::select_typed(&client, None, None, &[], None, None, None).await?;
+    /// ```
+    pub async fn client(&self, is_read_only: bool) -> Result<PgPoolClient<'_>, SQLError> {
+        Ok(PgPoolClient {
+            client: self.connection(is_read_only).get().await?,
+            query_lib_path: &self.query_lib_path,
+        })
+    }
+
+    /// This function runs `f` inside a transaction on the write pool
+    ///
+    /// The transaction is committed if `f` returns `Ok`, and rolled back if it returns `Err` (or panics)
+    ///
+    /// The future of `f` can only borrow `tx`, so any other data should be moved into it; to borrow data (e.g.
+    /// arguments of the caller), use [`PgPoolClient::begin`] instead
+    ///
+    /// ```no_run
+    /// let id = pool.transaction(|tx| Box::pin(async move {
+    ///     let id = Order::next_id(tx, None, "id").await?;
+    ///     Order::insert(tx, None, None, &[&id, &total]).await?;
+    ///     Ok(id)
+    /// })).await?;
+    /// ```
+    ///
+    /// [`PgPoolClient::begin`]: struct.PgPoolClient.html#method.begin
+    pub async fn transaction<T, F>(&self, f: F) -> Result<T, SQLError>
+    where
+        T: Send,
+        F: for<'t> FnOnce(&'t PgTransaction<'_>) -> BoxFuture<'t, Result<T, SQLError>> + Send,
+    {
+        self.client(false).await?.transaction(f).await
+    }
+
+    /// Like [`transaction`], but runs the whole transaction again (on a new connection) if it fails with
+    /// an error that can be retried (e.g. serialization failure or deadlock, see `SQLRetry`)
+    ///
+    /// ```no_run
+    /// let retry = SQLRetry::new(5).on_retry(|e, attempt, delay| warn!("{} {:?} {}", attempt, delay, e));
+    /// pool.transaction_retry(&retry, |tx| Box::pin(async move {
+    ///     let total: f64 = Order::sum(tx, None, "total", None, &[]).await?;
+    ///     Report::insert(tx, None, Some(vec!["total"]), &[&total]).await
+    /// })).await?;
+    /// ```
+    ///
+    /// As with [`transaction`], `f` can not borrow data of the caller; to borrow it, retry a whole
+    /// [`PgPoolClient::begin`] transaction with `SQLRetry::run`
+    ///
+    /// ```no_run
+    /// async fn place(pool: &PgPools, retry: &SQLRetry, order: &Order) -> Result<(), SQLError> {
+    ///     retry
+    ///         .run(|| async {
+    ///             let mut client = pool.client(false).await?;
+    ///             let tx = client.begin().await?;
+    ///             Order::insert(&tx, None, None, &[&order.id, &order.total]).await?;
+    ///             tx.commit().await
+    ///         })
+    ///         .await
+    /// }
+    /// ```
+    ///
+    /// [`transaction`]: #method.transaction
+    /// [`PgPoolClient::begin`]: struct.PgPoolClient.html#method.begin
+    pub async fn transaction_retry<T, F>(&self, retry: &SQLRetry, f: F) -> Result<T, SQLError>
+    where
+        T: Send,
+        F: for<'t> Fn(&'t PgTransaction<'_>) -> BoxFuture<'t, Result<T, SQLError>> + Send + Sync,
+    {
+        retry.run(|| self.transaction(&f)).await
+    }
+}
+
+#[async_trait]
+impl PgExecutor for PgPools {
+    fn query_lib_path(&self) -> &str {
+        &self.query_lib_path
+    }
+
+    async fn acquire(&self, is_read_only: bool) -> Result<PgConnection<'_>, SQLError> {
+        Ok(PgConnection::Pooled(Box::new(
+            self.connection(is_read_only).get().await?,
+        )))
+    }
+}
+
+/// This struct provides a client taken from `PgPools` and path of query libraris
+///
+/// The client goes back to its pool when this struct is dropped
+pub struct PgPoolClient<'a> {
+    pub client: Client,
+    pub query_lib_path: &'a str,
+}
+
+impl PgPoolClient<'_> {
+    /// This function begins a transaction on this client; it is rolled back if it is dropped without `commit`
+    ///
+    /// Unlike [`transaction`], queries of the transaction can borrow any data (e.g. arguments of the caller)
+    ///
+    /// ```no_run
+    /// async fn place(pool: &PgPools, order: &Order) -> Result<(), SQLError> {
+    ///     let mut client = pool.client(false).await?;
+    ///     let tx = client.begin().await?;
+    ///     Order::insert(&tx, None, None, &[&order.id, &order.total]).await?;
+    ///     Stock::update(&tx, None, vec!["amount"], &[&order.amount], Some(vec![SQLCondition::EQUAL("id")]), &[&order.stock_id]).await?;
+    ///     tx.commit().await
+    /// }
+    /// ```
+    ///
+    /// [`transaction`]: #method.transaction
+    pub async fn begin(&mut self) -> Result<PgTransaction<'_>, SQLError> {
+        Ok(PgTransaction::new(
+            self.client.transaction().await?,
+            self.query_lib_path,
+        ))
+    }
+
+    /// Like [`PgPools::transaction`], but runs the transaction on this client
+    ///
+    /// [`PgPools::transaction`]: struct.PgPools.html#method.transaction
+    pub async fn transaction<T, F>(&mut self, f: F) -> Result<T, SQLError>
+    where
+        T: Send,
+        F: for<'t> FnOnce(&'t PgTransaction<'_>) -> BoxFuture<'t, Result<T, SQLError>> + Send,
+    {
+        let transaction = self.begin().await?;
+        match f(&transaction).await {
+            Ok(value) => {
+                transaction.commit().await?;
+                Ok(value)
+            }
+            Err(err) => {
+                if let Err(rollback_err) = transaction.rollback().await {
+                    warn!("{:?}", rollback_err);
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PgExecutor for PgPoolClient<'_> {
+    fn query_lib_path(&self) -> &str {
+        self.query_lib_path
+    }
+
+    async fn acquire(&self, _is_read_only: bool) -> Result<PgConnection<'_>, SQLError> {
+        Ok(PgConnection::Borrowed(
+            &self.client,
+            &self.client.statement_cache,
+        ))
+    }
 }
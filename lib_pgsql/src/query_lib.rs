@@ -0,0 +1,211 @@
+use crate::common::SQLError;
+use std::collections::HashMap;
+use std::fs::{read_dir, read_to_string};
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// A loaded query lib, or its load error
+type CachedQueryLib = Result<Arc<SQLQueryLib>, String>;
+
+/// Loaded query libs (or their load errors) by their path
+static QUERY_LIBS: OnceLock<RwLock<HashMap<String, CachedQueryLib>>> = OnceLock::new();
+
+/// This struct keeps the SQL files of a query lib folder (and its sub folders) in memory
+///
+/// Each `.sql` file is a query by its path in the folder (e.g. `QueryType::LIB("reports/daily.sql")`), and a file
+/// can also have several named queries, each starting with a `-- name: ...` line (e.g. `QueryType::LIB("get_user")`)
+///
+/// ```sql
+/// -- name: get_user
+/// SELECT * FROM public.users WHERE id = $1;
+///
+/// -- name: delete_user
+/// DELETE FROM public.users WHERE id = $1;
+/// ```
+///
+/// Query names should be unique in a query lib; a missing query is `SQLError::QueryNotFoundError`
+#[derive(Debug, Default)]
+pub struct SQLQueryLib {
+    queries: HashMap<String, String>,
+}
+
+impl SQLQueryLib {
+    /// Reads all `.sql` files of the `path` folder
+    pub fn load(path: &str) -> Result<Self, SQLError> {
+        let mut query_lib = Self::default();
+        query_lib.load_folder(Path::new(path), "")?;
+        Ok(query_lib)
+    }
+
+    fn load_folder(&mut self, folder: &Path, prefix: &str) -> Result<(), SQLError> {
+        for entry in read_dir(folder)? {
+            let entry = entry?;
+            let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                self.load_folder(&entry.path(), &format!("{}/", name))?;
+            } else if name.ends_with(".sql") {
+                self.add(&name, &read_to_string(entry.path())?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the content of a file as query `file_name`, and its `-- name: ...` queries
+    pub fn add(&mut self, file_name: &str, content: &str) -> Result<(), SQLError> {
+        self.insert(file_name, content.trim())?;
+        let mut current: Option<(&str, Vec<&str>)> = None;
+        for line in content.lines() {
+            match line.trim().strip_prefix("-- name:") {
+                Some(name) => {
+                    if let Some((name, lines)) = current.take() {
+                        self.insert(name, lines.join("\n").trim())?;
+                    }
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return Err(format!("Empty query name in {}!", file_name).into());
+                    }
+                    current = Some((name, Vec::new()));
+                }
+                None => {
+                    if let Some((_, lines)) = current.as_mut() {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+        if let Some((name, lines)) = current {
+            self.insert(name, lines.join("\n").trim())?;
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, name: &str, query: &str) -> Result<(), SQLError> {
+        match self.queries.insert(name.to_owned(), query.to_owned()) {
+            None => Ok(()),
+            Some(_) => Err(format!("Duplicate query {} in query lib!", name).into()),
+        }
+    }
+
+    /// Returns query `name` (a file name or a `-- name: ...` query)
+    pub fn query(&self, name: &str) -> Result<&str, SQLError> {
+        self.queries
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| SQLError::QueryNotFoundError(name.to_owned()))
+    }
+
+    /// Returns `true` if there is query `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.queries.contains_key(name)
+    }
+
+    /// Returns names of all queries
+    pub fn names(&self) -> Vec<&str> {
+        self.queries.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the cached query lib of `path`, without reading any file; it is an error if the query lib is not
+    /// loaded (by [`init`] or [`reload`]) or its loading is failed
+    ///
+    /// [`init`]: #method.init
+    /// [`reload`]: #method.reload
+    pub fn cached(path: &str) -> Result<Arc<Self>, SQLError> {
+        let query_libs = QUERY_LIBS.get_or_init(Default::default);
+        match query_libs.read().map_err(|e| e.to_string())?.get(path) {
+            None => Err(format!("Query lib {} is not loaded!", path).into()),
+            Some(Ok(query_lib)) => Ok(query_lib.clone()),
+            Some(Err(e)) => Err(format!("Query lib {} is not loaded: {}", path, e).into()),
+        }
+    }
+
+    /// Loads the query lib of `path` if it is not loaded (or failed) before, and returns it like [`cached`]
+    ///
+    /// [`cached`]: #method.cached
+    pub fn init(path: &str) -> Result<Arc<Self>, SQLError> {
+        let query_libs = QUERY_LIBS.get_or_init(Default::default);
+        if query_libs
+            .read()
+            .map_err(|e| e.to_string())?
+            .contains_key(path)
+        {
+            return Self::cached(path);
+        }
+        Self::reload(path)
+    }
+
+    /// Reads the query lib of `path` again (e.g. after changing its files) and replaces the cached one; a load
+    /// error is cached too, so queries of a missing query lib do not read its files again
+    pub fn reload(path: &str) -> Result<Arc<Self>, SQLError> {
+        let query_lib = Self::load(path).map(Arc::new);
+        QUERY_LIBS
+            .get_or_init(Default::default)
+            .write()
+            .map_err(|e| e.to_string())?
+            .insert(
+                path.to_owned(),
+                query_lib
+                    .as_ref()
+                    .map(Arc::clone)
+                    .map_err(|e| e.to_string()),
+            );
+        query_lib
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_named_queries() {
+        let mut query_lib = SQLQueryLib::default();
+        let content = "-- users\n-- name: get_user\nSELECT * FROM users WHERE id = $1;\n\n-- name: delete_user \nDELETE FROM users WHERE id = $1;\n";
+        query_lib.add("users.sql", content).unwrap();
+        assert_eq!(query_lib.query("users.sql").unwrap(), content.trim());
+        assert_eq!(
+            query_lib.query("get_user").unwrap(),
+            "SELECT * FROM users WHERE id = $1;"
+        );
+        assert_eq!(
+            query_lib.query("delete_user").unwrap(),
+            "DELETE FROM users WHERE id = $1;"
+        );
+        assert!(!query_lib.contains("-- users"));
+        assert_eq!(query_lib.names().len(), 3);
+        assert!(matches!(
+            query_lib.query("missing"),
+            Err(SQLError::QueryNotFoundError(name)) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn add_rejects_names() {
+        let mut query_lib = SQLQueryLib::default();
+        assert!(query_lib
+            .add("a.sql", "-- name: a\nSELECT 1;\n-- name: a\nSELECT 2;")
+            .is_err());
+        let mut query_lib = SQLQueryLib::default();
+        assert!(query_lib.add("a.sql", "-- name:\nSELECT 1;").is_err());
+        let mut query_lib = SQLQueryLib::default();
+        query_lib.add("a.sql", "SELECT 1;").unwrap();
+        assert!(query_lib.add("a.sql", "SELECT 2;").is_err());
+        assert!(query_lib.add("b.sql", "-- name: a.sql\nSELECT 2;").is_err());
+    }
+
+    #[test]
+    fn cached_load_error() {
+        let path = std::env::temp_dir().join(format!("lib_pgsql_query_lib_{}", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let _ = std::fs::remove_dir_all(&path);
+        assert!(SQLQueryLib::cached(path_str).is_err());
+        assert!(SQLQueryLib::init(path_str).is_err());
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("a.sql"), "SELECT 1;").unwrap();
+        assert!(SQLQueryLib::init(path_str).is_err());
+        assert!(SQLQueryLib::cached(path_str).is_err());
+        assert!(SQLQueryLib::reload(path_str).unwrap().contains("a.sql"));
+        assert!(SQLQueryLib::cached(path_str).unwrap().contains("a.sql"));
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
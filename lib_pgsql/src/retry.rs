@@ -0,0 +1,164 @@
+use crate::common::{SQLError, SQLErrorKind};
+use log::warn;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A function that is called before each retry with the error, the number of the failed attempt and the delay
+pub type SQLRetryHook = Arc<dyn Fn(&SQLError, u32, Duration) + Send + Sync>;
+
+/// This struct provides settings for retrying an operation on transient errors
+///
+/// Serialization failures (`40001`) and deadlocks (`40P01`) are always retried; connection errors (e.g. pool
+/// timeout or lost connection) are only retried if `is_read_only = true`, since a write may have been done
+///
+/// Each retry waits for an exponential delay (`base_delay * 2^n`, maximum `max_delay`) with jitter (a random
+/// delay between half and all of it), and calls `on_retry` (or logs a warning)
+///
+/// ```no_run
+/// let retry = SQLRetry::new(5).read_only();
+/// let rows = retry
+///     .run(|| async { ExampleTable::select_typed(&pool, None, None, &[], None, None, None).await })
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct SQLRetry {
+    pub attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub is_read_only: bool,
+    pub on_retry: Option<SQLRetryHook>,
+}
+
+impl SQLRetry {
+    /// Runs maximum `attempts` times (including the first one), with the default delays (50ms to 2s)
+    pub fn new(attempts: u32) -> Self {
+        Self {
+            attempts,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(2),
+            is_read_only: false,
+            on_retry: None,
+        }
+    }
+
+    /// Sets the delay of the first retry and the maximum delay
+    pub fn backoff(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Retries connection errors too; only for operations that are safe to repeat
+    pub fn read_only(mut self) -> Self {
+        self.is_read_only = true;
+        self
+    }
+
+    /// Calls `hook` before each retry (e.g. for logging), instead of the default warning
+    pub fn on_retry<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&SQLError, u32, Duration) + Send + Sync + 'static,
+    {
+        self.on_retry = Some(Arc::new(hook));
+        self
+    }
+
+    /// Returns `true` if the operation can be run again after `error`
+    pub fn is_retryable(&self, error: &SQLError) -> bool {
+        match error.kind() {
+            SQLErrorKind::SERIALIZATION_FAILURE | SQLErrorKind::DEADLOCK => true,
+            SQLErrorKind::CONNECTION_LOST => self.is_read_only,
+            _ => false,
+        }
+    }
+
+    /// Returns the delay before the retry of failed `attempt` (starting from `1`)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        let half = delay / 2;
+        half + Duration::from_nanos(fastrand::u64(0..=half.as_nanos() as u64))
+    }
+
+    /// Runs `f` until it succeeds, returns an error that can not be retried, or all attempts are failed
+    ///
+    /// `f` creates a new future for each attempt, so it should do the whole unit of work (e.g. a transaction,
+    /// see `PgPools::transaction_retry`)
+    pub async fn run<T, F, Fut>(&self, mut f: F) -> Result<T, SQLError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SQLError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match f().await {
+                Err(error) if attempt < self.attempts && self.is_retryable(&error) => {
+                    let delay = self.delay(attempt);
+                    match &self.on_retry {
+                        Some(hook) => hook(&error, attempt, delay),
+                        None => warn!(
+                            "Attempt {} of {} failed, retry in {:?}: {}",
+                            attempt, self.attempts, delay, error
+                        ),
+                    }
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for SQLRetry {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deadpool::managed::TimeoutType;
+    use deadpool_postgres::PoolError;
+
+    #[test]
+    fn delay_backoff() {
+        let retry = SQLRetry::new(5).backoff(Duration::from_millis(100), Duration::from_secs(1));
+        for _ in 0..100 {
+            let delay = retry.delay(1);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+            let delay = retry.delay(3);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+            let delay = retry.delay(u32::MAX);
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+        let delays: Vec<Duration> = (0..100).map(|_| retry.delay(1)).collect();
+        assert!(delays.iter().any(|delay| *delay != delays[0]));
+        assert_eq!(
+            SQLRetry::new(5)
+                .backoff(Duration::ZERO, Duration::ZERO)
+                .delay(2),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn retryable_errors() {
+        let retry = SQLRetry::new(3);
+        let read_only = SQLRetry::new(3).read_only();
+        let timeout = SQLError::PoolError(PoolError::Timeout(TimeoutType::Wait));
+        assert!(!retry.is_retryable(&timeout));
+        assert!(read_only.is_retryable(&timeout));
+        for error in [
+            SQLError::PoolError(PoolError::Closed),
+            SQLError::NotFoundError,
+            SQLError::StringError("error".to_owned()),
+        ] {
+            assert!(!retry.is_retryable(&error) && !read_only.is_retryable(&error));
+        }
+    }
+}
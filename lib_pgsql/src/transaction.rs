@@ -0,0 +1,61 @@
+use crate::common::SQLError;
+use crate::executor::{PgConnection, PgExecutor};
+use async_trait::async_trait;
+use deadpool_postgres::Transaction;
+
+/// This struct provides an open PostgreSQL transaction and path of query libraris
+///
+/// It can be passed to every `DPQueryable` function instead of `PgPools`; all of them run on the
+/// transaction's connection, so `is_read_only` is ignored. Dropping it without `commit` rolls back.
+///
+/// It is started by `PgPoolClient::begin`, or by `PgPools::transaction` for a closure
+///
+/// ```no_run
+/// let mut client = pool.client(false).await?;
+/// let tx = client.begin().await?;
+/// Order::insert(&tx, None, None, &[&order.id, &order.total]).await?;
+/// tx.commit().await?;
+///
+/// pool.transaction(|tx| Box::pin(async move {
+///     Order::insert(tx, None, None, &[&order.id, &order.total]).await?;
+///     Stock::update(tx, None, vec!["amount"], &[&amount], Some(vec![SQLCondition::EQUAL("id")]), &[&id]).await
+/// })).await?;
+/// ```
+pub struct PgTransaction<'a> {
+    pub transaction: Transaction<'a>,
+    pub query_lib_path: &'a str,
+}
+
+impl<'a> PgTransaction<'a> {
+    /// This function wraps a started deadpool transaction
+    pub fn new(transaction: Transaction<'a>, query_lib_path: &'a str) -> Self {
+        Self {
+            transaction,
+            query_lib_path,
+        }
+    }
+
+    /// Consumes the transaction, committing all changes made within it
+    pub async fn commit(self) -> Result<(), SQLError> {
+        Ok(self.transaction.commit().await?)
+    }
+
+    /// Rolls the transaction back, discarding all changes made within it
+    pub async fn rollback(self) -> Result<(), SQLError> {
+        Ok(self.transaction.rollback().await?)
+    }
+}
+
+#[async_trait]
+impl PgExecutor for PgTransaction<'_> {
+    fn query_lib_path(&self) -> &str {
+        self.query_lib_path
+    }
+
+    async fn acquire(&self, _is_read_only: bool) -> Result<PgConnection<'_>, SQLError> {
+        Ok(PgConnection::Borrowed(
+            self.transaction.client(),
+            &self.transaction.statement_cache,
+        ))
+    }
+}
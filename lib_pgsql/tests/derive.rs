@@ -0,0 +1,88 @@
+use lib_pgsql::*;
+
+#[derive(Debug, Table, FromRow, ToSql, FromSql, Serialize, Deserialize)]
+#[table(name = "public.orders")]
+struct Order {
+    #[column(identity, primary_key)]
+    id: i64,
+    #[column(rename = "order")]
+    order_no: i32,
+    #[column(skip_insert)]
+    created_at: String,
+    name: String,
+}
+
+#[derive(Debug, Table, FromRow, ToSql, FromSql, Serialize, Deserialize)]
+struct OrderLine {
+    #[column(primary_key)]
+    order_id: i64,
+    #[column(primary_key)]
+    line: i32,
+}
+
+#[test]
+fn derive_columns() {
+    assert_eq!(Order::table_name(), "public.orders");
+    assert_eq!(
+        Order::columns(),
+        &[
+            SQLColumn {
+                name: "id",
+                field: "id",
+                primary_key: true,
+                skip_insert: true,
+                identity: true,
+            },
+            SQLColumn {
+                name: "order",
+                field: "order_no",
+                primary_key: false,
+                skip_insert: false,
+                identity: false,
+            },
+            SQLColumn {
+                name: "created_at",
+                field: "created_at",
+                primary_key: false,
+                skip_insert: true,
+                identity: false,
+            },
+            SQLColumn {
+                name: "name",
+                field: "name",
+                primary_key: false,
+                skip_insert: false,
+                identity: false,
+            },
+        ]
+    );
+    assert_eq!(Order::ORDER_NO, Order::columns()[1]);
+    assert_eq!(Order::primary_key(), vec!["id"]);
+    assert_eq!(Order::insert_field_list(), Some(vec!["order", "name"]));
+}
+
+#[test]
+fn derive_default_table_name() {
+    assert_eq!(OrderLine::table_name(), "order_line");
+    assert_eq!(OrderLine::primary_key(), vec!["order_id", "line"]);
+    assert_eq!(
+        OrderLine::insert_field_list(),
+        Some(vec!["order_id", "line"])
+    );
+}
+
+#[test]
+fn derive_query_builders() {
+    assert_eq!(
+        Order::field_query_builder(None).unwrap(),
+        "\"id\", \"order\" AS \"order_no\", \"created_at\", \"name\""
+    );
+    assert_eq!(
+        Order::insert_query_builder(None, None, 2).unwrap(),
+        "INSERT INTO \"public\".\"orders\" (\"order\", \"name\") VALUES ($1, $2)"
+    );
+    assert_eq!(
+        Order::insert_query_builder(None, Some(vec!["id", "name"]), 2).unwrap(),
+        "INSERT INTO \"public\".\"orders\" (\"id\", \"name\") OVERRIDING SYSTEM VALUE VALUES ($1, $2)"
+    );
+}
@@ -0,0 +1,198 @@
+use lib_pgsql::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Table, FromRow, ToSql, FromSql, Serialize, Deserialize)]
+#[table(name = "public.lib_pgsql_tx_items")]
+struct TxItem {
+    #[column(primary_key, skip_insert)]
+    id: i64,
+    name: String,
+}
+
+/// Returns pools of the test database, or `None` (the test is skipped) if `PGHOST` is not set; the other
+/// settings come from `PGUSER`, `PGPASSWORD`, `PGDATABASE` and `PGPORT`
+fn test_pool() -> Option<PgPools> {
+    let host = std::env::var("PGHOST").ok()?;
+    let var =
+        |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_owned());
+    let port = var("PGPORT", "5432").parse().unwrap();
+    Some(PgPools::new(
+        &var("PGUSER", "postgres"),
+        &var("PGPASSWORD", ""),
+        &var("PGDATABASE", "postgres"),
+        &host,
+        port,
+        2,
+        &host,
+        port,
+        2,
+        "".to_owned(),
+    ))
+}
+
+/// Creates an empty test table `name`
+async fn create_table(pool: &PgPools, name: &str) {
+    let query = format!(
+        "DROP TABLE IF EXISTS {0}; CREATE TABLE {0} (id bigserial PRIMARY KEY, name text NOT NULL)",
+        name
+    );
+    pool.client(false)
+        .await
+        .unwrap()
+        .client
+        .batch_execute(&query)
+        .await
+        .unwrap();
+}
+
+async fn drop_table(pool: &PgPools, name: &str) {
+    let query = format!("DROP TABLE {}", name);
+    TxItem::execute(pool, QueryType::RAW(query), &[], false)
+        .await
+        .unwrap();
+}
+
+async fn insert(
+    executor: &(impl PgExecutor + ?Sized),
+    table: &str,
+    name: &str,
+) -> Result<u64, SQLError> {
+    TxItem::insert(executor, Some(table), None, &[&name]).await
+}
+
+#[tokio::test]
+async fn begin_commit() {
+    let Some(pool) = test_pool() else { return };
+    let table = "public.lib_pgsql_tx_commit";
+    create_table(&pool, table).await;
+    let name = String::from("a");
+    let mut client = pool.client(false).await.unwrap();
+    let tx = client.begin().await.unwrap();
+    insert(&tx, table, &name).await.unwrap();
+    assert_eq!(TxItem::count(&tx, Some(table), None, &[]).await.unwrap(), 1);
+    assert_eq!(
+        TxItem::count(&pool, Some(table), None, &[]).await.unwrap(),
+        0
+    );
+    tx.commit().await.unwrap();
+    drop(client);
+    assert_eq!(
+        TxItem::count(&pool, Some(table), None, &[]).await.unwrap(),
+        1
+    );
+    drop_table(&pool, table).await;
+}
+
+#[tokio::test]
+async fn begin_rollback() {
+    let Some(pool) = test_pool() else { return };
+    let table = "public.lib_pgsql_tx_rollback";
+    create_table(&pool, table).await;
+    let mut client = pool.client(false).await.unwrap();
+    let tx = client.begin().await.unwrap();
+    insert(&tx, table, "a").await.unwrap();
+    tx.rollback().await.unwrap();
+    {
+        let tx = client.begin().await.unwrap();
+        insert(&tx, table, "b").await.unwrap();
+    }
+    assert_eq!(
+        TxItem::count(&client, Some(table), None, &[])
+            .await
+            .unwrap(),
+        0
+    );
+    drop(client);
+    drop_table(&pool, table).await;
+}
+
+#[tokio::test]
+async fn closure_transaction() {
+    let Some(pool) = test_pool() else { return };
+    let table = "public.lib_pgsql_tx_closure";
+    create_table(&pool, table).await;
+    let result: Result<(), SQLError> = pool
+        .transaction(|tx| {
+            Box::pin(async move {
+                insert(tx, table, "a").await?;
+                Err("Abort!".to_owned().into())
+            })
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(
+        TxItem::count(&pool, Some(table), None, &[]).await.unwrap(),
+        0
+    );
+    let count = pool
+        .transaction(|tx| {
+            Box::pin(async move {
+                insert(tx, table, "b").await?;
+                TxItem::count(tx, Some(table), None, &[]).await
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(
+        TxItem::count(&pool, Some(table), None, &[]).await.unwrap(),
+        1
+    );
+    drop_table(&pool, table).await;
+}
+
+/// Fails with a serialization failure (`40001`), which `SQLRetry` retries
+async fn serialization_failure(executor: &(impl PgExecutor + ?Sized)) -> Result<u64, SQLError> {
+    let query = "DO $$ BEGIN RAISE EXCEPTION 'retry' USING ERRCODE = '40001'; END $$";
+    TxItem::execute(executor, QueryType::RAW(query.to_owned()), &[], false).await
+}
+
+#[tokio::test]
+async fn transaction_retry() {
+    let Some(pool) = test_pool() else { return };
+    let table = "public.lib_pgsql_tx_retry";
+    create_table(&pool, table).await;
+    let retry = SQLRetry::new(3).backoff(Duration::from_millis(1), Duration::from_millis(1));
+    let attempts = Arc::new(AtomicU32::new(0));
+    let counter = attempts.clone();
+    pool.transaction_retry(&retry, move |tx| {
+        let counter = counter.clone();
+        Box::pin(async move {
+            insert(tx, table, "a").await?;
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                serialization_failure(tx).await?;
+            }
+            Ok(())
+        })
+    })
+    .await
+    .unwrap();
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        TxItem::count(&pool, Some(table), None, &[]).await.unwrap(),
+        1
+    );
+
+    let name = String::from("b");
+    let attempts = AtomicU32::new(0);
+    retry
+        .run(|| async {
+            let mut client = pool.client(false).await?;
+            let tx = client.begin().await?;
+            insert(&tx, table, &name).await?;
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                serialization_failure(&tx).await?;
+            }
+            tx.commit().await
+        })
+        .await
+        .unwrap();
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        TxItem::count(&pool, Some(table), None, &[]).await.unwrap(),
+        2
+    );
+    drop_table(&pool, table).await;
+}
@@ -0,0 +1,223 @@
+//! This library provides `#[derive(Table)]` for `lib_pgsql`
+//!
+//! It implements `DPQueryable` (and so `Queryable`) for a struct with named fields, including `table_name`,
+//! the static list of `columns`, `row_values` and `parse_type_at`
+//!
+//! ```no_run
+//! #[derive(Debug, Table, FromRow, ToSql, FromSql, Serialize, Deserialize)]
+//! #[table(name = "public.example_table")]
+//! struct ExampleTable {
+//!     #[column(primary_key, skip_insert)]
+//!     id: i64,
+//!     #[column(rename = "full_name")]
+//!     name: String,
+//! }
+//! ```
+//!
+//! `#[table(name = ...)]` is the PostgreSQL table; the snake case name of the struct is used if it is missing
+//!
+//! `#[column(primary_key)]` marks the primary key columns
+//!
+//! `#[column(rename = ...)]` is the PostgreSQL column of a field; it is selected as `column AS field`
+//!
+//! `#[column(skip_insert)]` leaves the column out of default `INSERT` fields (e.g. `serial` or generated columns)
+//!
+//! `#[column(identity)]` marks a `GENERATED ALWAYS AS IDENTITY` column; it implies `skip_insert`, and `INSERT`
+//! queries that explicitly set it use `OVERRIDING SYSTEM VALUE`
+//!
+//! A `SQLColumn` constant is generated for each field, named as the upper case field (e.g. `ExampleTable::NAME`);
+//! the constants are associated constants of an inherent `impl ExampleTable` block (not a separate module),
+//! so a user-defined associated constant with the same name (e.g. `const NAME: &str`) is a compile error
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr};
+
+/// This struct keeps the attributes of one field
+struct Column {
+    ident: Ident,
+    name: String,
+    primary_key: bool,
+    skip_insert: bool,
+    identity: bool,
+}
+
+/// This function implements `DPQueryable` for a struct (see crate documentation)
+#[proc_macro_derive(Table, attributes(table, column))]
+pub fn derive_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match table_impl(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// This function generates the `DPQueryable` implementation
+fn table_impl(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Table can not be derived for generic structs",
+        ));
+    }
+    let ident = &input.ident;
+    let table_name = table_name(&input)?;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "Table can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                ident,
+                "Table can only be derived for structs",
+            ))
+        }
+    };
+    let columns = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("Named field");
+            let mut column = Column {
+                name: ident.to_string().trim_start_matches("r#").to_owned(),
+                ident,
+                primary_key: false,
+                skip_insert: false,
+                identity: false,
+            };
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("column"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("primary_key") {
+                        column.primary_key = true;
+                        Ok(())
+                    } else if meta.path.is_ident("skip_insert") {
+                        column.skip_insert = true;
+                        Ok(())
+                    } else if meta.path.is_ident("identity") {
+                        column.identity = true;
+                        column.skip_insert = true;
+                        Ok(())
+                    } else if meta.path.is_ident("rename") {
+                        column.name = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else {
+                        Err(meta.error("Unknown column attribute"))
+                    }
+                })?;
+            }
+            Ok(column)
+        })
+        .collect::<Result<Vec<Column>, Error>>()?;
+    let column_consts = columns.iter().map(|column| {
+        let name = &column.name;
+        let field = column.ident.to_string();
+        let field = field.trim_start_matches("r#");
+        let primary_key = column.primary_key;
+        let skip_insert = column.skip_insert;
+        let identity = column.identity;
+        let const_ident = column_const(column);
+        let doc = format!("Column `{}` of `{}`", name, table_name.value());
+        quote! {
+            #[doc = #doc]
+            pub const #const_ident: ::lib_pgsql::SQLColumn = ::lib_pgsql::SQLColumn {
+                name: #name,
+                field: #field,
+                primary_key: #primary_key,
+                skip_insert: #skip_insert,
+                identity: #identity,
+            };
+        }
+    });
+    let column_items = columns.iter().map(column_const);
+    let value_items = columns.iter().map(|column| {
+        let ident = &column.ident;
+        quote! { &row.#ident }
+    });
+    let parse_items = columns.iter().enumerate().map(|(index, column)| {
+        let ident = &column.ident;
+        quote! { #ident: row.try_get(offset + #index)? }
+    });
+    Ok(quote! {
+        impl #ident {
+            #(#column_consts)*
+        }
+
+        impl<'a> ::lib_pgsql::DPQueryable<'a> for #ident {
+            type RowType = Self;
+
+            fn table_name() -> &'static str {
+                #table_name
+            }
+
+            fn columns() -> &'static [::lib_pgsql::SQLColumn] {
+                &[#(Self::#column_items),*]
+            }
+
+            fn row_values(
+                row: &Self::RowType,
+            ) -> Result<Vec<&(dyn ::lib_pgsql::ToSql + Sync)>, ::lib_pgsql::SQLError> {
+                Ok(vec![#(#value_items),*])
+            }
+
+            fn parse_type_at(
+                row: &::lib_pgsql::Row,
+                offset: usize,
+            ) -> Result<Self::RowType, ::lib_pgsql::SQLError> {
+                Ok(Self {
+                    #(#parse_items),*
+                })
+            }
+        }
+    })
+}
+
+/// This function returns the name of the constant of a column (upper case field name)
+fn column_const(column: &Column) -> Ident {
+    let field = column.ident.to_string();
+    Ident::new(
+        &field.trim_start_matches("r#").to_uppercase(),
+        column.ident.span(),
+    )
+}
+
+/// This function reads `#[table(name = ...)]`, or converts the struct name to snake case
+fn table_name(input: &DeriveInput) -> Result<LitStr, Error> {
+    let mut table_name = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("table"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                table_name = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Unknown table attribute"))
+            }
+        })?;
+    }
+    Ok(match table_name {
+        Some(name) => name,
+        None => {
+            let mut name = String::new();
+            for (index, c) in input.ident.to_string().chars().enumerate() {
+                if c.is_uppercase() && index > 0 {
+                    name.push('_');
+                }
+                name.extend(c.to_lowercase());
+            }
+            LitStr::new(&name, Span::call_site())
+        }
+    })
+}
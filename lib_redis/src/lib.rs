@@ -14,6 +14,7 @@ pub struct Redis;
 
 impl Redis {
     /// This function creates Redis pool connection
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(url: &str) -> Result<RdPool, CreatePoolError> {
         let config = Config::from_url(url);
         config.create_pool(Some(Tokio1))
@@ -62,10 +63,7 @@ impl Redis {
             .arg(&[key, value])
             .query_async::<_, ()>(&mut client)
             .await;
-        match res {
-            Ok(_) => true,
-            Err(_) => false,
-        }
+        res.is_ok()
     }
 
     /// This is redis `DEL` command
@@ -73,10 +71,7 @@ impl Redis {
         let mut client = pool.get().await.unwrap();
         let res: Result<_, RedisError> =
             cmd("DEL").arg(&key).query_async::<_, ()>(&mut client).await;
-        match res {
-            Ok(_) => true,
-            Err(_) => false,
-        }
+        res.is_ok()
     }
 
     /// This is redis `EXISTS` command
@@ -93,9 +88,6 @@ impl Redis {
         let res: Result<_, RedisError> = Cmd::expire(key, time)
             .query_async::<_, ()>(&mut client)
             .await;
-        match res {
-            Ok(_) => true,
-            Err(_) => false,
-        }
+        res.is_ok()
     }
 }